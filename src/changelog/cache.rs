@@ -0,0 +1,56 @@
+//! # Cache of pull request commits
+//!
+//! Commits of pull requests are cached on disk, keyed by the repository, the milestone
+//! and the time of the last update of the pull request, so regenerating the changelog
+//! for the same milestone queries only pull requests updated in the meantime.
+//!
+//! Only commits of pull requests are cached. Issues and the list of pull requests,
+//! which provides the time of the last update, are still retrieved on every run.
+
+use crate::utils::{read_file, write_file};
+use std::path::{Path, PathBuf};
+
+/// Cache of outputs of commands retrieving commits of pull requests.
+pub struct Cache {
+  /// Directory of cached entries of the repository and milestone.
+  dir: PathBuf,
+}
+
+impl Cache {
+  /// Creates the cache of the repository and milestone, located in the specified cache directory.
+  pub fn new(cache_dir: &Path, repository: &str, milestone: &str) -> Self {
+    let mut dir = cache_dir.join("changelog");
+    for name in repository.split('/') {
+      dir.push(sanitize(name));
+    }
+    dir.push(sanitize(milestone));
+    Self { dir }
+  }
+
+  /// Returns the cached output for the pull request, when it was not updated since it was cached.
+  pub fn get(&self, number: &str, updated_at: &str) -> Option<String> {
+    let contents = read_file(self.path(number)).ok()?;
+    let (cached_updated_at, output) = contents.split_once('\n')?;
+    (cached_updated_at == updated_at).then(|| output.to_string())
+  }
+
+  /// Stores the output for the pull request, failures are ignored, because caching only speeds up retrieval.
+  pub fn put(&self, number: &str, updated_at: &str, output: &str) {
+    _ = write_file(self.path(number), &format!("{updated_at}\n{output}"));
+  }
+
+  /// Returns the path of the cached entry of the pull request.
+  fn path(&self, number: &str) -> PathBuf {
+    self.dir.join(format!("pull-{}", sanitize(number)))
+  }
+}
+
+/// Returns the name usable as a file name, characters other than alphanumerics, `-`, `_` and `.` are replaced with `_`.
+fn sanitize(name: &str) -> String {
+  let name = name
+    .chars()
+    .map(|ch| if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') { ch } else { '_' })
+    .collect::<String>();
+  // Empty names and names consisting of dots only would point to the current or parent directory.
+  if name.chars().all(|ch| ch == '.') { "_".repeat(name.len().max(1)) } else { name }
+}
@@ -2,97 +2,237 @@
 //!
 //! Generates kind of reasonable changelog based on Git commits between revisions,
 //! GitHub pull requests and GitHub issues belonging to the same milestone.
+//! Commands are executed concurrently and commits of pull requests are cached on disk.
+
+mod cache;
 
 use crate::errors::*;
-use crate::utils;
-use crate::utils::SEPARATOR_LINE;
+use crate::repository::Repository;
+use crate::utils::{SEPARATOR_LINE, parallel_map};
+use cache::Cache;
 use regex::Regex;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
+use std::path::PathBuf;
 use std::sync::LazyLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Default maximum number of concurrently executed commands.
+pub const DEFAULT_JOBS: usize = 8;
 
 /// Pattern for matching pull request numbers with the preceding hash.
 const PULL_REQUEST_NUMBER_PATTERN: &str = r#"#\d+"#;
 
 /// Regular expression for matching pull request numbers with the preceding hash.
-pub static RE_PULL_REQUEST_NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(PULL_REQUEST_NUMBER_PATTERN).unwrap());
+static RE_PULL_REQUEST_NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(PULL_REQUEST_NUMBER_PATTERN).unwrap());
 
 /// The commit.
-#[derive(Clone)]
-struct Commit {
+#[derive(Debug, Clone)]
+pub struct Commit {
   /// Full commit hash.
-  hash: String,
+  pub hash: String,
   /// Commit title (subject).
-  subject: String,
+  pub subject: String,
 }
 
 /// The issue.
-#[derive(Clone)]
-struct Issue {
+#[derive(Debug, Clone)]
+pub struct Issue {
   /// Issue number.
-  number: String,
+  pub number: String,
   /// Issue title.
-  title: String,
+  pub title: String,
   /// Issue URL on GitHub.
-  url: String,
+  pub url: String,
 }
 
 /// The pull request.
-#[derive(Clone)]
-struct PullRequest {
+#[derive(Debug, Clone)]
+pub struct PullRequest {
   /// Pull request number.
-  number: String,
+  pub number: String,
   /// Pull request title.
-  title: String,
+  pub title: String,
   /// Pull request URL on GitHub.
-  url: String,
+  pub url: String,
   /// List of commits that constitute this pull request.
-  commits: Vec<Commit>,
+  pub commits: Vec<Commit>,
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn get_changelog(
-  verbose: bool,
-  start_revision: &str,
-  end_revision: &str,
-  milestone: &str,
-  repository: &str,
-  dir: &str,
-  exclude_commit: Vec<String>,
-  exclude_pr: Vec<String>,
-) -> Result<String> {
-  if verbose {
-    println!("\nCOMMANDS");
-    println!("{SEPARATOR_LINE}");
-  }
-  // Retrieve issues with specified milestone from GitHub repository.
-  let issues = get_issues(verbose, milestone, repository)?;
-  // Retrieve pull requests with specified milestone from GitHub repository.
-  let pull_requests = get_pull_requests(verbose, milestone, repository)?;
-  // Retrieve commits in specified recision range.
-  let commits = get_commits(verbose, dir, start_revision, end_revision)?;
-
-  if verbose {
-    println!("\nISSUES");
-    println!("{SEPARATOR_LINE}");
-    for issue in &issues {
-      println!("{} | {} | {}", issue.number, issue.title, issue.url);
-    }
-    println!("\nPULL REQUESTS");
-    println!("{SEPARATOR_LINE}");
-    for pull_request in &pull_requests {
-      println!("{} | {} | {}", pull_request.number, pull_request.title, pull_request.url);
-      for commit in &pull_request.commits {
-        println!("  {} | {}", commit.hash, commit.subject);
-      }
-    }
-    println!("\nCOMMITS");
-    println!("{SEPARATOR_LINE}");
-    for commit in &commits {
-      println!("{} | {}", commit.hash, commit.subject);
+/// Event reported while commands are executed.
+pub enum Event<'a> {
+  /// Command is about to be executed.
+  Started {
+    /// Executed command with arguments.
+    command: &'a str,
+    /// Number of the command, starting from one.
+    current: usize,
+    /// Number of commands known so far, grows when pull requests are retrieved.
+    total: usize,
+  },
+  /// Command finished successfully.
+  Finished {
+    /// Executed command with arguments.
+    command: &'a str,
+    /// Duration of the command.
+    duration: Duration,
+    /// Standard output of the command.
+    output: &'a str,
+  },
+  /// Commits of the pull request were taken from the cache, without executing the command.
+  Cached {
+    /// Pull request number.
+    number: &'a str,
+  },
+}
+
+/// Callback invoked with events of executed commands, possibly from multiple threads.
+type EventCallback = Box<dyn Fn(&Event) + Send + Sync>;
+
+/// Options of the changelog generator.
+pub struct Options {
+  /// Revision starting the range of commits.
+  start_revision: String,
+  /// Revision ending the range of commits.
+  end_revision: String,
+  /// Milestone of issues and pull requests.
+  milestone: String,
+  /// GitHub repository, like `EngosSoftware/magg`.
+  repository: String,
+  /// Directory of the Git repository.
+  dir: String,
+  /// Patterns of subjects of excluded commits.
+  exclude_commits: Vec<String>,
+  /// Patterns of titles of excluded pull requests.
+  exclude_pull_requests: Vec<String>,
+  /// Maximum number of concurrently executed commands.
+  jobs: usize,
+  /// Directory of the cache, caching is disabled when not specified.
+  cache_dir: Option<PathBuf>,
+  /// Callback invoked with events of executed commands.
+  on_event: Option<EventCallback>,
+}
+
+impl Options {
+  /// Creates options for the changelog of commits between revisions and issues and pull requests in the milestone.
+  pub fn new(start_revision: impl Into<String>, end_revision: impl Into<String>, milestone: impl Into<String>, repository: impl Into<String>) -> Self {
+    Self {
+      start_revision: start_revision.into(),
+      end_revision: end_revision.into(),
+      milestone: milestone.into(),
+      repository: repository.into(),
+      dir: ".".to_string(),
+      exclude_commits: vec![],
+      exclude_pull_requests: vec![],
+      jobs: DEFAULT_JOBS,
+      cache_dir: crate::utils::cache_dir().map(|dir| dir.join("magg")),
+      on_event: None,
     }
   }
 
+  /// Sets the directory of the Git repository, defaults to the current directory.
+  pub fn dir(mut self, dir: impl Into<String>) -> Self {
+    self.dir = dir.into();
+    self
+  }
+
+  /// Excludes commits with subjects containing the pattern.
+  pub fn exclude_commit(mut self, pattern: impl Into<String>) -> Self {
+    self.exclude_commits.push(pattern.into());
+    self
+  }
+
+  /// Excludes pull requests with titles containing the pattern.
+  pub fn exclude_pull_request(mut self, pattern: impl Into<String>) -> Self {
+    self.exclude_pull_requests.push(pattern.into());
+    self
+  }
+
+  /// Sets the maximum number of concurrently executed commands, defaults to [DEFAULT_JOBS].
+  pub fn jobs(mut self, jobs: usize) -> Self {
+    self.jobs = jobs.max(1);
+    self
+  }
+
+  /// Sets the directory of the cache, defaults to `magg` in the cache directory of the user.
+  pub fn cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+    self.cache_dir = Some(dir.into());
+    self
+  }
+
+  /// Disables the cache, commits of all pull requests are retrieved from GitHub.
+  pub fn no_cache(mut self) -> Self {
+    self.cache_dir = None;
+    self
+  }
+
+  /// Sets the callback invoked before and after each command is executed, for reporting progress.
+  pub fn on_event(mut self, callback: impl Fn(&Event) + Send + Sync + 'static) -> Self {
+    self.on_event = Some(Box::new(callback));
+    self
+  }
+}
+
+/// Generated changelog.
+#[derive(Debug, Clone)]
+pub struct Changelog {
+  /// GitHub repository, like `EngosSoftware/magg`.
+  pub repository: String,
+  /// Issues in the milestone.
+  pub issues: Vec<Issue>,
+  /// Pull requests in the milestone.
+  pub pull_requests: Vec<PullRequest>,
+  /// Commits between revisions.
+  pub commits: Vec<Commit>,
+  /// Excluded commits with the matching pattern.
+  pub excluded_commits: Vec<(Commit, String)>,
+  /// Excluded pull requests with the matching pattern.
+  pub excluded_pull_requests: Vec<(PullRequest, String)>,
+  /// Issues listed in the changelog, sorted by number in descending order.
+  pub listed_issues: Vec<Issue>,
+  /// Pull requests listed in the changelog, sorted by number in descending order.
+  pub listed_pull_requests: Vec<PullRequest>,
+  /// Commits listed in the changelog, not belonging to any listed pull request.
+  pub listed_commits: Vec<Commit>,
+  /// Warnings about commits referencing pull requests outside the milestone.
+  pub warnings: Vec<String>,
+}
+
+/// Generates the changelog based on commits, pull requests and issues.
+pub fn get_changelog(options: &Options) -> Result<Changelog> {
+  let Options {
+    start_revision,
+    end_revision,
+    milestone,
+    repository,
+    dir,
+    exclude_commits,
+    exclude_pull_requests,
+    ..
+  } = options;
+  let session = Session {
+    options,
+    started: AtomicUsize::new(0),
+    total: AtomicUsize::new(3),
+  };
+  let (issues, pull_requests, commits) = std::thread::scope(|scope| {
+    // Retrieve issues with specified milestone from GitHub repository.
+    let issues = scope.spawn(|| get_issues(&session, milestone, repository));
+    // Retrieve pull requests with specified milestone from GitHub repository.
+    let pull_requests = scope.spawn(|| get_pull_requests(&session, milestone, repository));
+    // Retrieve commits in specified revision range.
+    let commits = get_commits(&session, dir, start_revision, end_revision);
+    (
+      issues.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)),
+      pull_requests.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)),
+      commits,
+    )
+  });
+  let (issues, pull_requests, commits) = (issues?, pull_requests?, commits?);
+
   // Move all commits to the map skipping excluded commits.
   let mut commit_map = HashMap::new();
   for commit in &commits {
@@ -100,16 +240,11 @@ pub fn get_changelog(
   }
 
   // Remove excluded commits from the map.
-  if verbose {
-    println!("\nEXCLUDED COMMITS:");
-    println!("{SEPARATOR_LINE}");
-  }
+  let mut excluded_commits = vec![];
   for commit in &commits {
-    for pattern in &exclude_commit {
+    for pattern in exclude_commits {
       if commit.subject.contains(pattern) {
-        if verbose {
-          println!("{} | {} | {}", commit.hash, commit.subject, pattern);
-        }
+        excluded_commits.push((commit.clone(), pattern.clone()));
         commit_map.remove(&commit.hash);
       }
     }
@@ -147,120 +282,246 @@ pub fn get_changelog(
   }
 
   // Remove all excluded pull requests from the map.
-  if verbose {
-    println!("\nEXCLUDED PULL REQUESTS");
-    println!("{SEPARATOR_LINE}");
-  }
+  let mut excluded_pull_requests = vec![];
   for pull_request in &pull_requests {
-    for pattern in &exclude_pr {
+    for pattern in exclude_pull_requests {
       if pull_request.title.contains(pattern) {
-        if verbose {
-          println!("{} | {} | {} | {}", pull_request.number, pull_request.title, pull_request.url, pattern);
-        }
+        excluded_pull_requests.push((pull_request.clone(), pattern.clone()));
         pull_request_map.remove(&pull_request.number);
       }
     }
   }
 
-  // Prepare the string buffer for the changelog content.
-  let mut changelog = String::new();
-  // Write issue names.
-  for issue in issue_sorted_map.values().rev() {
-    let _ = writeln!(&mut changelog, "- {} ([#{}])", issue.title, issue.number);
-  }
-  // Write pull request names.
-  for pull_request in pull_request_map.values().rev() {
-    let _ = writeln!(&mut changelog, "- {} ([#{}])", pull_request.title, pull_request.number);
-  }
-  // Write commit names.
-  for commit in commit_map.values() {
-    let _ = writeln!(&mut changelog, "- {} ([0x{}])", commit.subject, &commit.hash[..7]);
-  }
-  let _ = writeln!(&mut changelog);
-  // Write issue links.
-  for issue in issue_sorted_map.values().rev() {
-    let _ = writeln!(&mut changelog, "[#{}]: {}", issue.number, issue.url);
-  }
-  // Write pull request links.
-  for pull_request in pull_request_map.values().rev() {
-    let _ = writeln!(&mut changelog, "[#{}]: {}", pull_request.number, pull_request.url);
-  }
-  // Write commit links.
-  for commit in commit_map.values() {
-    let _ = writeln!(&mut changelog, "[0x{}]: https://github.com/{repository}/commit/{}", &commit.hash[..7], commit.hash);
+  Ok(Changelog {
+    repository: repository.clone(),
+    listed_issues: issue_sorted_map.into_values().rev().collect(),
+    listed_pull_requests: pull_request_map.into_values().rev().collect(),
+    listed_commits: commit_map.into_values().collect(),
+    warnings: warnings.into_values().rev().collect(),
+    issues,
+    pull_requests,
+    commits,
+    excluded_commits,
+    excluded_pull_requests,
+  })
+}
+
+impl Changelog {
+  /// Renders the changelog entries in Markdown, with commit links pointing to the specified repository.
+  /// Warnings are not rendered, they are reported separately.
+  pub fn render(&self, repository: &Repository) -> String {
+    // Prepare the string buffer for the changelog content.
+    let mut changelog = String::new();
+    // Write issue names.
+    for issue in &self.listed_issues {
+      let _ = writeln!(&mut changelog, "- {} ([#{}])", issue.title, issue.number);
+    }
+    // Write pull request names.
+    for pull_request in &self.listed_pull_requests {
+      let _ = writeln!(&mut changelog, "- {} ([#{}])", pull_request.title, pull_request.number);
+    }
+    // Write commit names.
+    for commit in &self.listed_commits {
+      let _ = writeln!(&mut changelog, "- {} ([0x{}])", commit.subject, &commit.hash[..7]);
+    }
+    let _ = writeln!(&mut changelog);
+    // Write issue links.
+    for issue in &self.listed_issues {
+      let _ = writeln!(&mut changelog, "[#{}]: {}", issue.number, issue.url);
+    }
+    // Write pull request links.
+    for pull_request in &self.listed_pull_requests {
+      let _ = writeln!(&mut changelog, "[#{}]: {}", pull_request.number, pull_request.url);
+    }
+    // Write commit links.
+    for commit in &self.listed_commits {
+      let _ = writeln!(&mut changelog, "[0x{}]: {}", &commit.hash[..7], repository.commit_url(&commit.hash));
+    }
+    changelog
   }
 
-  if !warnings.is_empty() {
-    let _ = writeln!(&mut changelog, "\nWARNINGS:");
-    for warning in warnings.values().rev() {
-      let _ = writeln!(&mut changelog, "{}", warning);
+  /// Renders retrieved issues, pull requests and commits, and excluded ones, for verbose reports.
+  pub fn render_details(&self) -> String {
+    let mut details = String::new();
+    _ = writeln!(&mut details, "\nISSUES");
+    _ = writeln!(&mut details, "{SEPARATOR_LINE}");
+    for issue in &self.issues {
+      _ = writeln!(&mut details, "{} | {} | {}", issue.number, issue.title, issue.url);
+    }
+    _ = writeln!(&mut details, "\nPULL REQUESTS");
+    _ = writeln!(&mut details, "{SEPARATOR_LINE}");
+    for pull_request in &self.pull_requests {
+      _ = writeln!(&mut details, "{} | {} | {}", pull_request.number, pull_request.title, pull_request.url);
+      for commit in &pull_request.commits {
+        _ = writeln!(&mut details, "  {} | {}", commit.hash, commit.subject);
+      }
+    }
+    _ = writeln!(&mut details, "\nCOMMITS");
+    _ = writeln!(&mut details, "{SEPARATOR_LINE}");
+    for commit in &self.commits {
+      _ = writeln!(&mut details, "{} | {}", commit.hash, commit.subject);
     }
+    _ = writeln!(&mut details, "\nEXCLUDED COMMITS:");
+    _ = writeln!(&mut details, "{SEPARATOR_LINE}");
+    for (commit, pattern) in &self.excluded_commits {
+      _ = writeln!(&mut details, "{} | {} | {}", commit.hash, commit.subject, pattern);
+    }
+    _ = writeln!(&mut details, "\nEXCLUDED PULL REQUESTS");
+    _ = writeln!(&mut details, "{SEPARATOR_LINE}");
+    for (pull_request, pattern) in &self.excluded_pull_requests {
+      _ = writeln!(&mut details, "{} | {} | {} | {}", pull_request.number, pull_request.title, pull_request.url, pattern);
+    }
+    details
   }
-  Ok(changelog)
 }
 
-fn parse_issues(input: String) -> Result<Vec<Issue>> {
-  let mut issues = vec![];
-  let rows = parse_columns(input, 3)?;
-  for columns in rows {
-    issues.push(Issue {
-      number: columns[0].to_string(),
-      title: columns[1].to_string(),
-      url: columns[2].to_string(),
-    });
-  }
-  Ok(issues)
+/// Returns tags of the Git repository in the specified directory, sorted by version, the highest first.
+pub fn recent_tags(dir: &str, limit: usize) -> Result<Vec<String>> {
+  let stdout = run_command("git", &["tag", "--sort=-version:refname"], dir)?;
+  Ok(stdout.lines().map(|line| line.trim().to_string()).filter(|tag| !tag.is_empty()).take(limit).collect())
+}
+
+/// Returns titles of open milestones and of recently closed milestones of the GitHub repository.
+pub fn recent_milestones(repository: &str, closed_limit: usize) -> Result<Vec<String>> {
+  let endpoint = format!("repos/{repository}/milestones?state=all&per_page=100");
+  let records = parse_records::<MilestoneRecord>(&run_command("gh", &["api", endpoint.as_str()], ".")?, "milestone")?;
+  let mut open = records
+    .iter()
+    .filter(|record| record.state == "open")
+    .map(|record| record.title.clone())
+    .collect::<Vec<String>>();
+  let mut closed = records.iter().filter(|record| record.state == "closed").collect::<Vec<&MilestoneRecord>>();
+  // Timestamps in ISO 8601 format are sorted chronologically when sorted as strings.
+  closed.sort_by(|a, b| b.closed_at.cmp(&a.closed_at));
+  open.extend(closed.into_iter().take(closed_limit).map(|record| record.title.clone()));
+  Ok(open)
+}
+
+/// Milestone as returned by GitHub API.
+#[derive(Deserialize)]
+struct MilestoneRecord {
+  /// Milestone title.
+  title: String,
+  /// Milestone state, `open` or `closed`.
+  state: String,
+  /// Time when the milestone was closed.
+  closed_at: Option<String>,
+}
+
+/// Issue as returned by `gh issue list`.
+#[derive(Deserialize)]
+struct IssueRecord {
+  /// Issue number.
+  number: u64,
+  /// Issue title.
+  title: String,
+  /// Issue URL on GitHub.
+  url: String,
+}
+
+/// Pull request as returned by `gh pr list`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestRecord {
+  /// Pull request number.
+  number: u64,
+  /// Pull request title.
+  title: String,
+  /// Pull request URL on GitHub.
+  url: String,
+  /// Time of the last update of the pull request.
+  updated_at: String,
 }
 
-fn get_issues(verbose: bool, milestone: &str, repository: &str) -> Result<Vec<Issue>> {
+/// Commit as returned by `gh pr view`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommitRecord {
+  /// Full commit hash.
+  oid: String,
+  /// Commit title (subject).
+  message_headline: String,
+}
+
+fn parse_issues(input: &str) -> Result<Vec<Issue>> {
+  let records = parse_records::<IssueRecord>(input, "issue")?;
+  Ok(
+    records
+      .into_iter()
+      .map(|record| Issue {
+        number: record.number.to_string(),
+        title: record.title,
+        url: record.url,
+      })
+      .collect(),
+  )
+}
+
+fn get_issues(session: &Session, milestone: &str, repository: &str) -> Result<Vec<Issue>> {
   let search = format!(r#"--search=milestone:{}"#, milestone);
   let repo = format!("--repo={}", repository);
-  let args = &[
-    "issue",
-    "list",
-    search.as_str(),
-    "--state=all",
-    "--limit=9999",
-    repo.as_str(),
-    "--json=number,title,url",
-    r#"--template='{{range .}}{{printf "%v ||| %s ||| %s\n" .number .title .url}}{{end}}'"#,
-  ];
-  let stdout = execute_command(verbose, "gh", args, ".")?;
-  parse_issues(stdout)
+  let args = &["issue", "list", search.as_str(), "--state=all", "--limit=9999", repo.as_str(), "--json=number,title,url"];
+  let stdout = execute_command(session, "gh", args, ".")?;
+  parse_issues(&stdout)
 }
 
-fn get_pull_request_commits(verbose: bool, number: &str, repository: &str) -> Result<Vec<Commit>> {
-  // gh pr view 661 --repo=cosmwasm/wasmvm --json=commits --jq='.commits[] | "\(.oid) ||| \(.messageHeadline)"'
+/// Retrieves commits of the pull request, cached commits are used when the pull request was not updated in the meantime.
+fn get_pull_request_commits(session: &Session, cache: Option<&Cache>, number: &str, updated_at: &str, repository: &str) -> Result<Vec<Commit>> {
+  // Entries cached by previous versions may have a different format, such entries are retrieved again.
+  if let Some(commits) = cache
+    .and_then(|cache| cache.get(number, updated_at))
+    .and_then(|output| parse_pull_request_commits(&output).ok())
+  {
+    session.total.fetch_sub(1, Ordering::Relaxed);
+    if let Some(on_event) = &session.options.on_event {
+      on_event(&Event::Cached { number });
+    }
+    return Ok(commits);
+  }
+  // gh pr view 661 --repo=cosmwasm/wasmvm --json=commits --jq=.commits
   let repo = format!("--repo={}", repository);
-  let args = &[
-    "pr",
-    "view",
-    number,
-    repo.as_str(),
-    "--json=commits",
-    r#"--jq=.commits[]|"\(.oid) ||| \(.messageHeadline)""#,
-  ];
-  let stdout = execute_command(verbose, "gh", args, ".")?;
-  parse_commits(stdout)
+  let args = &["pr", "view", number, repo.as_str(), "--json=commits", "--jq=.commits"];
+  let stdout = execute_command(session, "gh", args, ".")?;
+  let commits = parse_pull_request_commits(&stdout)?;
+  if let Some(cache) = cache {
+    cache.put(number, updated_at, &stdout);
+  }
+  Ok(commits)
+}
+
+fn parse_pull_request_commits(input: &str) -> Result<Vec<Commit>> {
+  let records = parse_records::<CommitRecord>(input, "pull request commit")?;
+  Ok(
+    records
+      .into_iter()
+      .map(|record| Commit {
+        hash: record.oid,
+        subject: record.message_headline,
+      })
+      .collect(),
+  )
 }
 
-fn parse_pull_requests(verbose: bool, input: String, repository: &str) -> Result<Vec<PullRequest>> {
+fn parse_pull_requests(session: &Session, input: &str, milestone: &str, repository: &str) -> Result<Vec<PullRequest>> {
+  let records = parse_records::<PullRequestRecord>(input, "pull request")?;
+  session.total.fetch_add(records.len(), Ordering::Relaxed);
+  let cache = session.options.cache_dir.as_ref().map(|dir| Cache::new(dir, repository, milestone));
+  let commits = parallel_map(&records, session.options.jobs, |record| {
+    get_pull_request_commits(session, cache.as_ref(), &record.number.to_string(), &record.updated_at, repository)
+  });
   let mut pull_requests = vec![];
-  let rows = parse_columns(input, 3)?;
-  for columns in rows {
-    let number = columns[0].to_string();
-    let commits = get_pull_request_commits(verbose, &number, repository)?;
+  for (record, commits) in records.into_iter().zip(commits) {
     pull_requests.push(PullRequest {
-      number,
-      title: columns[1].to_string(),
-      url: columns[2].to_string(),
-      commits,
+      number: record.number.to_string(),
+      title: record.title,
+      url: record.url,
+      commits: commits?,
     });
   }
   Ok(pull_requests)
 }
 
-fn get_pull_requests(verbose: bool, milestone: &str, repository: &str) -> Result<Vec<PullRequest>> {
+fn get_pull_requests(session: &Session, milestone: &str, repository: &str) -> Result<Vec<PullRequest>> {
   let search = format!(r#"--search=milestone:{}"#, milestone);
   let repo = format!("--repo={}", repository);
   let args = &[
@@ -270,38 +531,81 @@ fn get_pull_requests(verbose: bool, milestone: &str, repository: &str) -> Result
     "--state=all",
     "--limit=9999",
     repo.as_str(),
-    "--json=number,title,url",
-    r#"--template='{{range .}}{{printf "%v ||| %s ||| %s\n" .number .title .url}}{{end}}'"#,
+    "--json=number,title,url,updatedAt",
   ];
-  let stdout = execute_command(verbose, "gh", args, ".")?;
-  parse_pull_requests(verbose, stdout, repository)
+  let stdout = execute_command(session, "gh", args, ".")?;
+  parse_pull_requests(session, &stdout, milestone, repository)
 }
 
-fn parse_commits(input: String) -> Result<Vec<Commit>> {
+/// Parses the output of `git log` with hashes and subjects of commits, terminated with NUL characters.
+fn parse_commits(input: &str) -> Result<Vec<Commit>> {
+  let fields = input.split_terminator('\0').collect::<Vec<&str>>();
   let mut commits = vec![];
-  let rows = parse_columns(input, 2)?;
-  for columns in rows {
-    commits.push(Commit {
-      hash: columns[0].to_string(),
-      subject: columns[1].to_string(),
-    });
+  for record in fields.chunks(2) {
+    match record {
+      [hash, subject] if !hash.is_empty() && hash.chars().all(|ch| ch.is_ascii_hexdigit()) => commits.push(Commit {
+        hash: hash.to_string(),
+        subject: subject.to_string(),
+      }),
+      _ => return Err(MaggError::parse(format!("invalid commit record: {:?}", record.join("\0")))),
+    }
   }
   Ok(commits)
 }
 
-fn get_commits(verbose: bool, dir: &str, start_revision: &str, end_revision: &str) -> Result<Vec<Commit>> {
+fn get_commits(session: &Session, dir: &str, start_revision: &str, end_revision: &str) -> Result<Vec<Commit>> {
   let revisions = format!("{}...{}", start_revision, end_revision);
-  let args = &["log", r#"--format="%H ||| %s""#, revisions.as_str(), "--"];
-  let stdout = execute_command(verbose, "git", args, dir)?;
-  parse_commits(stdout)
+  let args = &["log", "-z", "--format=%H%x00%s", revisions.as_str(), "--"];
+  let stdout = execute_command(session, "git", args, dir)?;
+  parse_commits(&stdout)
 }
 
-fn execute_command(verbose: bool, program: &str, args: &[&str], dir: &str) -> Result<String> {
-  if verbose {
-    println!("{} {}", program, args.join(" "));
-  } else {
-    utils::step_progress();
+/// Parses the JSON array of records, the offending record is reported when it does not match the expected structure.
+fn parse_records<T: DeserializeOwned>(input: &str, kind: &str) -> Result<Vec<T>> {
+  let records = serde_json::from_str::<Vec<serde_json::Value>>(input).map_err(|e| MaggError::parse(format!("failed to parse {kind} records")).with_source(e))?;
+  records
+    .into_iter()
+    .map(|record| serde_json::from_value(record.clone()).map_err(|e| MaggError::parse(format!("invalid {kind} record: {record}")).with_source(e)))
+    .collect()
+}
+
+/// Progress of commands executed concurrently while generating the changelog.
+struct Session<'a> {
+  /// Options of the changelog generator.
+  options: &'a Options,
+  /// Number of started commands.
+  started: AtomicUsize,
+  /// Number of commands known so far, grows when pull requests are retrieved.
+  total: AtomicUsize,
+}
+
+/// Executes the command, reporting its progress in the session.
+fn execute_command(session: &Session, program: &str, args: &[&str], dir: &str) -> Result<String> {
+  let command_line = format!("{} {}", program, args.join(" "));
+  let current = session.started.fetch_add(1, Ordering::Relaxed) + 1;
+  let total = session.total.load(Ordering::Relaxed).max(current);
+  let options = session.options;
+  if let Some(on_event) = &options.on_event {
+    on_event(&Event::Started {
+      command: &command_line,
+      current,
+      total,
+    });
+  }
+  let started = Instant::now();
+  let stdout = run_command(program, args, dir)?;
+  if let Some(on_event) = &options.on_event {
+    on_event(&Event::Finished {
+      command: &command_line,
+      duration: started.elapsed(),
+      output: &stdout,
+    });
   }
+  Ok(stdout)
+}
+
+/// Runs the command in the specified directory and returns its standard output.
+fn run_command(program: &str, args: &[&str], dir: &str) -> Result<String> {
   let mut command = std::process::Command::new(program);
   let child = command
     .args(args)
@@ -310,41 +614,16 @@ fn execute_command(verbose: bool, program: &str, args: &[&str], dir: &str) -> Re
     .stdout(std::process::Stdio::piped())
     .stderr(std::process::Stdio::piped())
     .spawn()
-    .map_err(|e| error_spawn_command(program, e.to_string()))?;
-  let output = child.wait_with_output().map_err(|e| error_obtain_output(e.to_string()))?;
+    .map_err(|e| error_spawn_command(program, e))?;
+  let output = child.wait_with_output().map_err(|e| error_obtain_output(program, e))?;
   let stdout = String::from_utf8_lossy(&output.stdout).to_string();
   let stderr = String::from_utf8_lossy(&output.stderr).to_string();
   let status = output.status;
   if status.success() {
     Ok(stdout)
+  } else if program == "gh" {
+    Err(MaggError::forge(format!("GitHub request failed: {} {}", program, args.join(" "))).with_source(error_execute_command(program, status, stdout, stderr)))
   } else {
-    Err(error_execute_command(status, stdout, stderr))
-  }
-}
-
-fn parse_columns(input: String, col_count: usize) -> Result<Vec<Vec<String>>> {
-  let mut rows = vec![];
-  for mut line in input.lines().map(|line| line.trim()) {
-    if line.starts_with("\"") {
-      line = line.strip_prefix("\"").unwrap();
-    }
-    if line.starts_with("'") {
-      line = line.strip_prefix("'").unwrap();
-    }
-    if line.ends_with("\"") {
-      line = line.strip_suffix("\"").unwrap();
-    }
-    if line.ends_with("'") {
-      line = line.strip_suffix("'").unwrap();
-    }
-    line = line.trim();
-    if !line.is_empty() {
-      let columns = line.split(" ||| ").map(|s| s.to_string()).collect::<Vec<String>>();
-      if columns.len() != col_count {
-        return Err(MaggError::new(format!("invalid number of columns, expected: {col_count}, actual: {}", columns.len())));
-      }
-      rows.push(columns);
-    }
+    Err(error_execute_command(program, status, stdout, stderr))
   }
-  Ok(rows)
 }
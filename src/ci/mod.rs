@@ -0,0 +1,158 @@
+//! # CI workflows generator
+//!
+//! Generates GitHub Actions workflows building and testing the package on Linux, Windows and macOS,
+//! matching the build badges in README.md. The Linux workflow additionally checks formatting,
+//! measures code coverage and, when `package.rust-version` is set, checks the minimal supported Rust version.
+//! The toolchain is taken from `rust-toolchain.toml` or `rust-toolchain` file, `stable` is used by default.
+//! GitHub runs only workflows located in the repository root, so members of a workspace
+//! share the workflows generated in the workspace root.
+
+use crate::errors::*;
+use crate::lint::Diagnostic;
+use crate::manifest::{Manifest, Workspace};
+use crate::repository::DEFAULT_BRANCH;
+use crate::utils::{absolute, parse_toml, read_file};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Directory of workflow files.
+const WORKFLOWS_DIR: &str = ".github/workflows";
+
+/// Default toolchain, used when no toolchain file is present.
+const DEFAULT_TOOLCHAIN: &str = "stable";
+
+/// Toolchain files, in the order of precedence.
+const TOOLCHAIN_FILE_NAMES: [&str; 2] = ["rust-toolchain.toml", "rust-toolchain"];
+
+/// Workflow template.
+const WORKFLOW: &str = include_str!("WORKFLOW");
+
+/// Template of jobs checking formatting and measuring code coverage.
+const CHECKS: &str = include_str!("CHECKS");
+
+/// Template of the job checking the minimal supported Rust version.
+const MSRV: &str = include_str!("MSRV");
+
+/// Generated workflows: file name, workflow name, runner, build target and the flag indicating
+/// if the workflow contains additional checks.
+const WORKFLOWS: [(&str, &str, &str, &str, bool); 4] = [
+  ("build-linux.yml", "Linux", "ubuntu-latest", "x86_64-unknown-linux-gnu", true),
+  ("build-windows.yml", "Windows", "windows-latest", "x86_64-pc-windows-msvc", false),
+  ("build-macos.yml", "macOs", "macos-latest", "x86_64-apple-darwin", false),
+  ("build-macos-arm64.yml", "macOS arm64", "macos-latest", "aarch64-apple-darwin", false),
+];
+
+/// Pattern for matching the workflow file name in the URL of the workflow status badge.
+const WORKFLOW_BADGE_PATTERN: &str = r#"/actions/workflows/([^/\s)]+)/badge\.svg"#;
+
+/// Regular expression for matching the workflow file name in the URL of the workflow status badge.
+static RE_WORKFLOW_BADGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(WORKFLOW_BADGE_PATTERN).unwrap());
+
+/// Returns the names and contents of workflow files.
+pub fn get_workflows(manifest: &Manifest) -> Result<Vec<(String, String)>> {
+  let toolchain = toolchain(manifest)?;
+  let msrv = manifest.package_str("rust-version")?;
+  Ok(
+    WORKFLOWS
+      .iter()
+      .map(|(file_name, name, runner, target, checks)| {
+        let mut content = WORKFLOW.to_string();
+        if *checks {
+          content.push_str(CHECKS);
+          if msrv.is_some() {
+            content.push_str(MSRV);
+          }
+        }
+        let content = content
+          .replace("::WORKFLOW_NAME::", name)
+          .replace("::BRANCH::", DEFAULT_BRANCH)
+          .replace("::RUNS_ON::", runner)
+          .replace("::TARGET::", target)
+          .replace("::TOOLCHAIN::", &toolchain)
+          .replace("::MSRV::", msrv.unwrap_or_default());
+        (format!("{WORKFLOWS_DIR}/{file_name}"), content)
+      })
+      .collect(),
+  )
+}
+
+/// Returns the directory where workflows of the package are generated: the workspace root,
+/// when the package belongs to a workspace, or the package directory.
+/// The workspace root located below the base directory is returned relative to the base directory.
+pub fn workflows_root(manifest: &Manifest, base_dir: &Path) -> Result<PathBuf> {
+  let Ok(workspace) = Workspace::find(manifest.dir()) else {
+    return Ok(manifest.dir().to_path_buf());
+  };
+  Ok(match workspace.dir().strip_prefix(absolute(base_dir)?) {
+    Ok(relative) => base_dir.join(relative),
+    Err(_) => workspace.dir().to_path_buf(),
+  })
+}
+
+/// Checks if every workflow status badge in README files in the specified directory
+/// points at an existing workflow file in the workflows root directory.
+pub fn check_badges(dir: &Path, root_dir: &Path) -> Result<Vec<Diagnostic>> {
+  let mut diagnostics = vec![];
+  for path in readme_files(dir)? {
+    for (index, line) in read_file(&path)?.lines().enumerate() {
+      for captures in RE_WORKFLOW_BADGE.captures_iter(line) {
+        let workflow = format!("{WORKFLOWS_DIR}/{}", &captures[1]);
+        if !root_dir.join(&workflow).exists() {
+          diagnostics.push(Diagnostic {
+            file_name: path.clone(),
+            line: index + 1,
+            message: format!("badge points at missing workflow file '{workflow}'"),
+          });
+        }
+      }
+    }
+  }
+  Ok(diagnostics)
+}
+
+/// Returns the toolchain channel from the toolchain file of the package or the workspace.
+fn toolchain(manifest: &Manifest) -> Result<String> {
+  let mut dirs = vec![manifest.dir().to_path_buf()];
+  if let Ok(workspace) = Workspace::find(manifest.dir()) {
+    dirs.push(workspace.dir().to_path_buf());
+  }
+  for dir in dirs {
+    for file_name in TOOLCHAIN_FILE_NAMES {
+      let path = dir.join(file_name);
+      if !path.exists() {
+        continue;
+      }
+      // The legacy `rust-toolchain` file may contain only the name of the channel.
+      let channel = match parse_toml(&path) {
+        Ok(parsed) => parsed
+          .get("toolchain")
+          .and_then(|toolchain| toolchain.get("channel"))
+          .and_then(|channel| channel.as_str())
+          .map(|channel| channel.to_string()),
+        Err(_) => read_file(&path)?.lines().next().map(|line| line.trim().to_string()),
+      };
+      return channel
+        .filter(|channel| !channel.is_empty())
+        .ok_or_else(|| MaggError::parse(format!("toolchain channel not found in {}", path.display())));
+    }
+  }
+  Ok(DEFAULT_TOOLCHAIN.to_string())
+}
+
+/// Returns sorted paths of README files in the directory, like `README.md` and `README.de.md`.
+fn readme_files(dir: &Path) -> Result<Vec<PathBuf>> {
+  let root = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+  if !root.exists() {
+    return Ok(vec![]);
+  }
+  let entries = std::fs::read_dir(root).map_err(|e| MaggError::io(format!("failed to list files in {}", root.display()), e))?;
+  let mut paths = entries
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.file_name().to_string_lossy().to_string())
+    .filter(|file_name| file_name.starts_with("README") && file_name.ends_with(".md"))
+    .map(|file_name| dir.join(file_name))
+    .collect::<Vec<PathBuf>>();
+  paths.sort();
+  Ok(paths)
+}
@@ -1,71 +1,331 @@
-use crate::code_of_conduct::get_code_of_conduct;
 use crate::errors::*;
-use crate::licenses::{get_apache_2, get_apache_notice, get_mit};
-use crate::utils::SEPARATOR_LINE;
-use crate::{changelog, readme, utils};
+use crate::headers::Status;
+use crate::lint::manifest::Severity;
+use crate::lint::markdown;
+use crate::manifest::{Manifest, Workspace};
+use crate::repository::Repository;
+use crate::utils::{FileStatus, SEPARATOR_LINE};
+use crate::writer::Writer;
+use crate::{changelog, ci, code_of_conduct, community, headers, licenses, lint, log, manifest, prompt, readme, release, scaffold, utils};
 use antex::{StyledText, Text, auto};
-use clap::{Arg, ArgAction, ArgMatches, Command, arg, command, crate_version};
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Command, arg, command, crate_version, value_parser};
+use clap_complete::{Generator, Shell};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Number of recent tags offered as changelog revisions.
+const RECENT_TAGS: usize = 10;
+
+/// Number of recently closed milestones offered as changelog milestones.
+const RECENT_CLOSED_MILESTONES: usize = 5;
+
+/// Options shared by all commands.
+struct Globals {
+  /// Flag indicating if the command is run for all workspace members.
+  workspace: bool,
+  /// Names of selected workspace members.
+  packages: Vec<String>,
+  /// Flag indicating if generated files may overwrite files with changed content.
+  force: bool,
+  /// Flag indicating if generated files are only previewed.
+  dry_run: bool,
+  /// Path of the package or workspace manifest.
+  manifest_path: Option<String>,
+  /// Directory of generated files.
+  output_dir: Option<String>,
+}
+
+impl Globals {
+  /// Returns the directory of the manifest specified with `--manifest-path`, or the current directory.
+  fn root_dir(&self) -> Result<PathBuf> {
+    let Some(manifest_path) = &self.manifest_path else {
+      return Ok(PathBuf::new());
+    };
+    let path = Path::new(manifest_path);
+    if path.file_name().is_none_or(|file_name| file_name != manifest::MANIFEST_FILE_NAME) {
+      return Err(MaggError::validation(format!("the manifest path must be a path to a Cargo.toml file: {manifest_path}")));
+    }
+    if !path.exists() {
+      return Err(MaggError::validation(format!("manifest path {manifest_path} does not exist")));
+    }
+    Ok(path.parent().unwrap_or(Path::new("")).to_path_buf())
+  }
+
+  /// Returns the directory of the record of generated files: the output directory, when specified,
+  /// or the root of the workspace containing the root directory, or the root directory.
+  fn record_dir(&self, root_dir: &Path) -> PathBuf {
+    match (&self.output_dir, Workspace::find(root_dir)) {
+      (Some(output_dir), _) => PathBuf::from(output_dir),
+      (None, Ok(workspace)) => workspace.dir().to_path_buf(),
+      (None, Err(_)) => root_dir.to_path_buf(),
+    }
+  }
+
+  /// Returns the directory of files generated for the package.
+  ///
+  /// Without `--output-dir`, files are generated in the package directory. Otherwise, files are generated
+  /// in the output directory, in subdirectories of workspace members located below the root directory.
+  fn output_dir(&self, root_dir: &Path, package_dir: &Path) -> PathBuf {
+    match &self.output_dir {
+      Some(output_dir) => Path::new(output_dir).join(package_dir.strip_prefix(root_dir).unwrap_or(Path::new(""))),
+      None => package_dir.to_path_buf(),
+    }
+  }
+}
+
+/// Arguments of the changelog subcommand.
+struct ChangelogArgs {
+  /// Start revision, when specified.
+  start_revision: Option<String>,
+  /// End revision, when specified.
+  end_revision: Option<String>,
+  /// Milestone, when specified.
+  milestone: Option<String>,
+  /// Organization/Repository name, when specified.
+  repository: Option<String>,
+  /// Directory of the Git repository, when specified.
+  dir: Option<String>,
+  /// String patterns for excluding commits by subject.
+  exclude_commit: Vec<String>,
+  /// String patterns for excluding pull requests by title.
+  exclude_pr: Vec<String>,
+  /// Flag indicating if missing values are selected interactively.
+  interactive: bool,
+  /// Maximum number of concurrently executed commands.
+  jobs: usize,
+  /// Flag indicating if the cache of pull request commits is bypassed.
+  no_cache: bool,
+  /// Tag of the release published with the changelog as release notes, when specified.
+  release_tag: Option<String>,
+  /// Paths of files attached to the published release.
+  attach: Vec<String>,
+  /// Base URL of the forge REST API, when specified.
+  api_url: Option<String>,
+}
 
 enum Action {
+  /// Scaffold a new crate repository.
+  New(
+    /// Directory of the new crate.
+    String,
+    /// Flag indicating if the directory must be new or empty.
+    bool,
+    /// Name of the crate.
+    Option<String>,
+    /// Authors of the crate.
+    Vec<String>,
+    /// Description of the crate.
+    Option<String>,
+    /// License of the crate.
+    Option<String>,
+    /// Repository URL.
+    Option<String>,
+    /// Flag indicating if the crate is a library.
+    bool,
+  ),
   /// Generate README.md file
   Readme(
-    /// Name of the file containing the body text of scaffolded README.md file.
-    String,
+    /// Names of the files containing the body text of scaffolded README files, one per language.
+    Vec<String>,
+    /// Flag indicating if the Rust examples should be tested instead of writing README.md file.
+    bool,
   ),
   /// Generate license files.
-  Licenses,
+  Licenses(
+    /// Flag indicating if the license files should only be checked.
+    bool,
+  ),
+  /// Audit licenses of dependencies and generate third-party notices.
+  LicensesAudit(
+    /// Flag indicating if notices should be generated even when licenses of some dependencies are unresolved.
+    bool,
+  ),
   /// Generate code of conduct file.
   CodeOfConduct,
+  /// Generate community health files.
+  Community(
+    /// Kind of generated files.
+    community::Kind,
+    /// Flag indicating if the files should only be checked.
+    bool,
+  ),
+  /// Generate CI workflow files.
+  Ci(
+    /// Flag indicating if the workflow files should only be checked.
+    bool,
+  ),
   /// Generate changelog.
-  Changelog(
-    /// Start revision.
-    String,
-    /// End revision.
-    String,
-    /// Milestone.
-    String,
-    /// Organization/Repository name.
-    String,
-    /// Current directory.
-    String,
-    /// Verbose flag.
+  Changelog(ChangelogArgs),
+  /// Add, update or check license headers in source files.
+  Headers(
+    /// Flag indicating if the headers should only be checked.
     bool,
-    /// String patterns for excluding commits by subject.
-    Vec<String>,
-    /// String patterns for excluding pull requests by title.
+  ),
+  /// Check Markdown files.
+  LintMarkdown(
+    /// Names of the checked files.
     Vec<String>,
   ),
+  /// Check the package manifest.
+  LintManifest,
+  /// Generate shell completions.
+  Completions(
+    /// Shell the completions are generated for.
+    Shell,
+  ),
+  /// Generate man pages.
+  Man,
   /// Do nothing.
   Nothing,
 }
 
 /// Parses CLI argument matches.
 fn get_matches() -> ArgMatches {
+  get_command().get_matches()
+}
+
+/// Returns the definition of the command-line interface.
+fn get_command() -> Command {
   command!()
     // disable the built-in version flag
     .disable_version_flag(true)
     // handle the version flag in a custom way
     .arg(Arg::new("version").short('V').long("version").help("Print version").action(ArgAction::SetTrue))
+    // Select workspace members.
+    .arg(
+      Arg::new("workspace")
+        .long("workspace")
+        .help("Run the command for all workspace members")
+        .action(ArgAction::SetTrue)
+        .global(true),
+    )
+    .arg(
+      Arg::new("package")
+        .short('p')
+        .long("package")
+        .value_name("CRATE")
+        .help("Run the command for the specified workspace member")
+        .action(ArgAction::Append)
+        .global(true),
+    )
+    // Resolve paths independently of the current directory.
+    .arg(
+      Arg::new("manifest-path")
+        .long("manifest-path")
+        .value_name("PATH")
+        .help("Path to Cargo.toml of the package or the workspace")
+        .global(true),
+    )
+    .arg(
+      Arg::new("output-dir")
+        .long("output-dir")
+        .value_name("DIR")
+        .help("Directory of generated files, defaults to the package directory")
+        .global(true),
+    )
+    // Select the verbosity of diagnostic output.
+    .arg(
+      Arg::new("verbose")
+        .short('v')
+        .long("verbose")
+        .help("Report executed commands and details on stderr, use -vv for debugging details")
+        .action(ArgAction::Count)
+        .global(true),
+    )
+    .arg(
+      Arg::new("quiet")
+        .short('q')
+        .long("quiet")
+        .help("Report only errors on stderr")
+        .action(ArgAction::SetTrue)
+        .conflicts_with("verbose")
+        .global(true),
+    )
+    // Protect files with changed content.
+    .arg(
+      Arg::new("force")
+        .long("force")
+        .help("Overwrite generated files even when their content has changed")
+        .action(ArgAction::SetTrue)
+        .global(true),
+    )
+    .arg(
+      Arg::new("dry-run")
+        .long("dry-run")
+        .help("Display changes of generated files without writing them")
+        .action(ArgAction::SetTrue)
+        .global(true),
+    )
+    // Document exit codes of error kinds.
+    .after_help(exit_codes_help())
+    // Scaffold new crate repositories.
+    .subcommand(scaffold_command("new", "Creates a new crate repository in the specified directory", 12).arg(arg!(<PATH>).help("Directory of the new crate")))
+    .subcommand(scaffold_command("init", "Creates a new crate repository in an existing directory", 13).arg(arg!([PATH]).help("Directory of the new crate").default_value(".")))
     // Generate README.md file.
     .subcommand(
       Command::new("readme")
         .about("Generates README.md file")
         .display_order(1)
-        .arg(arg!(<README_BODY>).help("File containing the body of the scaffolded README.md").required(true).index(1)),
+        .arg(
+          arg!(<README_BODY>...)
+            .help("Files containing the body of the scaffolded README.md, one per language, like docs/README.md docs/README.de.md")
+            .required(true)
+            .index(1),
+        )
+        .arg(
+          Arg::new("test-examples")
+            .long("test-examples")
+            .help("Test Rust examples from the scaffolded README.md instead of writing the file")
+            .action(ArgAction::SetTrue),
+        ),
+    )
+    .subcommand(
+      Command::new("licenses")
+        .about("Generates license files matching the license in Cargo.toml")
+        .display_order(2)
+        .arg(
+          Arg::new("check")
+            .long("check")
+            .help("Check license files without modifying them")
+            .action(ArgAction::SetTrue),
+        )
+        .subcommand(
+          Command::new("audit").about("Checks licenses of dependencies and generates THIRD-PARTY-NOTICES file").arg(
+            Arg::new("allow-missing")
+              .long("allow-missing")
+              .help("Generate notices even when licenses of some dependencies could not be resolved")
+              .action(ArgAction::SetTrue),
+          ),
+        ),
     )
-    .subcommand(Command::new("licenses").about("Generates MIT and Apache 2.0 license files").display_order(2))
     .subcommand(Command::new("code-of-conduct").about("Generates code of conduct file").display_order(3))
+    .subcommand(community_command("contributing", "Generates CONTRIBUTING.md file", 4))
+    .subcommand(community_command("security", "Generates SECURITY.md file", 5))
+    .subcommand(community_command("support", "Generates SUPPORT.md file", 6))
+    .subcommand(community_command("issue-templates", "Generates issue and pull request templates", 7))
+    .subcommand(
+      Command::new("ci")
+        .about("Generates CI workflow files matching build badges in README.md")
+        .display_order(8)
+        .arg(
+          Arg::new("check")
+            .long("check")
+            .help("Check workflow files and build badges without modifying files")
+            .action(ArgAction::SetTrue),
+        ),
+    )
     .subcommand(
       Command::new("changelog")
         .about("Generates changelog")
-        .display_order(4)
+        .display_order(9)
         .arg(
           Arg::new("start-revision")
             .short('s')
             .long("start")
             .help("Start revision for searching commits")
             .action(ArgAction::Set)
-            .required(true)
+            .required_unless_present("interactive")
             .display_order(1),
         )
         .arg(
@@ -74,7 +334,7 @@ fn get_matches() -> ArgMatches {
             .long("end")
             .help("End revision for searching commits")
             .action(ArgAction::Set)
-            .required(true)
+            .required_unless_present("interactive")
             .display_order(2),
         )
         .arg(
@@ -83,7 +343,7 @@ fn get_matches() -> ArgMatches {
             .long("milestone")
             .help("GitHub milestone name for searching issues and pull requests")
             .action(ArgAction::Set)
-            .required(true)
+            .required_unless_present("interactive")
             .display_order(3),
         )
         .arg(
@@ -92,29 +352,20 @@ fn get_matches() -> ArgMatches {
             .long("repo")
             .help("GitHub organization/repository name for searching issues and pull requests")
             .action(ArgAction::Set)
-            .required(true)
+            .required_unless_present("interactive")
             .display_order(4),
         )
         .arg(
           Arg::new("directory")
             .short('d')
             .long("dir")
-            .help("Directory of a Git repository for searching commits")
+            .help("Directory of a Git repository for searching commits, the directory of --manifest-path when not specified")
             .action(ArgAction::Set)
             .default_value(".")
             .default_missing_value(".")
             .num_args(0..=1)
             .display_order(5),
         )
-        .arg(
-          Arg::new("verbose")
-            .long("verbose")
-            .help("Set this flag to display more detailed report")
-            .action(ArgAction::SetTrue)
-            .default_value("false")
-            .default_missing_value("true")
-            .display_order(6),
-        )
         .arg(
           Arg::new("exclude-commit")
             .long("exclude-commit")
@@ -128,41 +379,259 @@ fn get_matches() -> ArgMatches {
             .help("Exclude pull requests that contain this text in title")
             .action(ArgAction::Append)
             .display_order(8),
+        )
+        .arg(
+          Arg::new("interactive")
+            .short('i')
+            .long("interactive")
+            .help("Select revisions, milestone and repository interactively")
+            .action(ArgAction::SetTrue)
+            .display_order(9),
+        )
+        .arg(
+          Arg::new("jobs")
+            .short('j')
+            .long("jobs")
+            .value_name("N")
+            .help("Maximum number of concurrently executed commands")
+            .action(ArgAction::Set)
+            .value_parser(value_parser!(u16).range(1..))
+            .default_value(changelog::DEFAULT_JOBS.to_string())
+            .display_order(10),
+        )
+        .arg(
+          Arg::new("no-cache")
+            .long("no-cache")
+            .help("Retrieve commits of all pull requests, bypassing the cache")
+            .action(ArgAction::SetTrue)
+            .display_order(11),
+        )
+        .arg(
+          Arg::new("publish-release")
+            .long("publish-release")
+            .value_name("TAG")
+            .help("Create or update the forge release of the tag with the changelog as release notes")
+            .action(ArgAction::Set)
+            .display_order(12),
+        )
+        .arg(
+          Arg::new("attach")
+            .long("attach")
+            .value_name("FILE")
+            .help("Attach the file to the published release")
+            .action(ArgAction::Append)
+            .requires("publish-release")
+            .display_order(13),
+        )
+        .arg(
+          Arg::new("api-url")
+            .long("api-url")
+            .value_name("URL")
+            .help("Base URL of the forge REST API, derived from the repository when not specified")
+            .action(ArgAction::Set)
+            .requires("publish-release")
+            .display_order(14),
         ),
     )
-    .get_matches()
+    .subcommand(
+      Command::new("headers").about("Adds or updates license headers in source files").display_order(10).arg(
+        Arg::new("check")
+          .long("check")
+          .help("Check license headers without modifying files")
+          .action(ArgAction::SetTrue),
+      ),
+    )
+    .subcommand(
+      Command::new("lint")
+        .about("Checks documentation and manifest files")
+        .display_order(11)
+        .subcommand_required(true)
+        .subcommand(
+          Command::new("markdown")
+            .about("Checks links and reference definitions in Markdown files")
+            .arg(arg!([FILES]...).help("Markdown files to be checked").default_value("README.md")),
+        )
+        .subcommand(Command::new("manifest").about("Checks Cargo.toml against crates.io rules and the team policy")),
+    )
+    // Generate files for packaging.
+    .subcommand(
+      Command::new("completions")
+        .about("Generates shell completions")
+        .display_order(14)
+        .arg(arg!(<SHELL>).help("Shell to generate completions for").value_parser(value_parser!(Shell))),
+    )
+    .subcommand(
+      Command::new("man")
+        .about("Generates man pages")
+        .long_about("Generates man pages of magg and its subcommands, only the magg.1 page is printed without --output-dir")
+        .display_order(15),
+    )
+}
+
+/// Returns the subcommand scaffolding crate repositories.
+fn scaffold_command(name: &'static str, about: &'static str, display_order: usize) -> Command {
+  Command::new(name)
+    .about(about)
+    .display_order(display_order)
+    .arg(Arg::new("name").long("name").value_name("NAME").help("Name of the crate, defaults to the directory name"))
+    .arg(
+      Arg::new("author")
+        .long("author")
+        .value_name("AUTHOR")
+        .help("Author of the crate, like 'Name <e-mail>', defaults to the Git user")
+        .action(ArgAction::Append),
+    )
+    .arg(Arg::new("description").long("description").value_name("TEXT").help("Description of the crate"))
+    .arg(
+      Arg::new("license")
+        .long("license")
+        .value_name("SPDX")
+        .help("License of the crate [default: MIT OR Apache-2.0]"),
+    )
+    .arg(Arg::new("repository").long("repository").value_name("URL").help("Repository URL of the crate"))
+    .arg(Arg::new("lib").long("lib").help("Create a library instead of a binary").action(ArgAction::SetTrue))
+}
+
+/// Returns the subcommand generating community health files.
+fn community_command(name: &'static str, about: &'static str, display_order: usize) -> Command {
+  Command::new(name)
+    .about(about)
+    .display_order(display_order)
+    .arg(Arg::new("check").long("check").help("Check files without modifying them").action(ArgAction::SetTrue))
 }
 
 /// Checks the list of arguments passed from the command line
-/// and returns an action related to a valid argument.
-fn get_cli_action() -> Action {
+/// and returns an action related to a valid argument,
+/// together with the selected workspace members.
+fn get_cli_action() -> (Action, Globals) {
   let matches = get_matches();
+  let mut globals = Globals {
+    workspace: false,
+    packages: vec![],
+    force: false,
+    dry_run: false,
+    manifest_path: None,
+    output_dir: None,
+  };
   // Replaces the built-in version flag with the custom handler.
   if matches.get_flag("version") {
     // Displays only the version number, without the name of the crate.
     println!("{}", crate_version!());
-    return Action::Nothing;
+    return (Action::Nothing, globals);
   }
+  if let Some((_, matches)) = matches.subcommand() {
+    globals.workspace = match_boolean(matches, "workspace");
+    globals.packages = match_strings(matches, "package");
+    globals.force = match_boolean(matches, "force");
+    globals.dry_run = match_boolean(matches, "dry-run");
+    globals.manifest_path = match_optional_string(matches, "manifest-path");
+    globals.output_dir = match_optional_string(matches, "output-dir");
+    log::set_level(match (match_boolean(matches, "quiet"), matches.get_count("verbose")) {
+      (true, _) => log::Level::Quiet,
+      (false, 0) => log::Level::Normal,
+      (false, 1) => log::Level::Verbose,
+      (false, _) => log::Level::Debug,
+    });
+  }
+  (get_subcommand_action(&matches), globals)
+}
+
+/// Returns the help text listing exit codes.
+fn exit_codes_help() -> String {
+  let mut help = "Exit codes:\n  0   success\n".to_string();
+  for (code, description) in EXIT_CODES {
+    _ = writeln!(&mut help, "  {code:<3} {description}");
+  }
+  help
+}
+
+/// Returns an action related to the subcommand.
+fn get_subcommand_action(matches: &ArgMatches) -> Action {
   match matches.subcommand() {
+    Some((name @ ("new" | "init"), matches)) => {
+      return Action::New(
+        match_string(matches, "PATH"),
+        name == "new",
+        match_optional_string(matches, "name"),
+        match_strings(matches, "author"),
+        match_optional_string(matches, "description"),
+        match_optional_string(matches, "license"),
+        match_optional_string(matches, "repository"),
+        match_boolean(matches, "lib"),
+      );
+    }
     Some(("readme", matches)) => {
-      return Action::Readme(match_string(matches, "README_BODY"));
+      return Action::Readme(match_strings(matches, "README_BODY"), match_boolean(matches, "test-examples"));
     }
-    Some(("licenses", _matches)) => {
-      return Action::Licenses;
+    Some(("licenses", matches)) => {
+      if let Some(("audit", matches)) = matches.subcommand() {
+        return Action::LicensesAudit(match_boolean(matches, "allow-missing"));
+      }
+      return Action::Licenses(match_boolean(matches, "check"));
     }
     Some(("code-of-conduct", _matches)) => {
       return Action::CodeOfConduct;
     }
+    Some(("contributing", matches)) => {
+      return Action::Community(community::Kind::Contributing, match_boolean(matches, "check"));
+    }
+    Some(("security", matches)) => {
+      return Action::Community(community::Kind::Security, match_boolean(matches, "check"));
+    }
+    Some(("support", matches)) => {
+      return Action::Community(community::Kind::Support, match_boolean(matches, "check"));
+    }
+    Some(("issue-templates", matches)) => {
+      return Action::Community(community::Kind::IssueTemplates, match_boolean(matches, "check"));
+    }
+    Some(("ci", matches)) => {
+      return Action::Ci(match_boolean(matches, "check"));
+    }
     Some(("changelog", matches)) => {
-      let start_revision = match_string(matches, "start-revision");
-      let end_revision = match_string(matches, "end-revision");
-      let milestone = match_string(matches, "milestone");
-      let repository = match_string(matches, "repository");
-      let dir = match_string(matches, "directory");
-      let verbose = match_boolean(matches, "verbose");
+      let start_revision = match_optional_string(matches, "start-revision");
+      let end_revision = match_optional_string(matches, "end-revision");
+      let milestone = match_optional_string(matches, "milestone");
+      let repository = match_optional_string(matches, "repository");
+      let dir = (matches.value_source("directory") != Some(ValueSource::DefaultValue)).then(|| match_string(matches, "directory"));
       let exclude_commit = match_strings(matches, "exclude-commit");
       let exclude_pr = match_strings(matches, "exclude-pr");
-      return Action::Changelog(start_revision, end_revision, milestone, repository, dir, verbose, exclude_commit, exclude_pr);
+      let interactive = match_boolean(matches, "interactive");
+      let jobs = matches.get_one::<u16>("jobs").copied().map(usize::from).unwrap_or(changelog::DEFAULT_JOBS);
+      let no_cache = match_boolean(matches, "no-cache");
+      let release_tag = match_optional_string(matches, "publish-release");
+      let attach = match_strings(matches, "attach");
+      let api_url = match_optional_string(matches, "api-url");
+      return Action::Changelog(ChangelogArgs {
+        start_revision,
+        end_revision,
+        milestone,
+        repository,
+        dir,
+        exclude_commit,
+        exclude_pr,
+        interactive,
+        jobs,
+        no_cache,
+        release_tag,
+        attach,
+        api_url,
+      });
+    }
+    Some(("headers", matches)) => {
+      return Action::Headers(match_boolean(matches, "check"));
+    }
+    Some(("lint", matches)) => match matches.subcommand() {
+      Some(("markdown", matches)) => return Action::LintMarkdown(match_strings(matches, "FILES")),
+      Some(("manifest", _matches)) => return Action::LintManifest,
+      _ => {}
+    },
+    Some(("completions", matches)) => {
+      if let Some(shell) = matches.get_one::<Shell>("SHELL") {
+        return Action::Completions(*shell);
+      }
+    }
+    Some(("man", _matches)) => {
+      return Action::Man;
     }
     _ => {}
   }
@@ -170,46 +639,608 @@ fn get_cli_action() -> Action {
 }
 
 pub fn do_action() {
-  fn error_message(reason: MaggError) -> Text {
+  fn error_message(reason: &MaggError) -> Text {
     auto().bold().red().s("error").reset().s(": ").s(reason.to_string())
   }
 
+  /// Displays the error with the hint how to resolve it and exits with the exit code of the error.
+  fn fail(reason: MaggError) -> ! {
+    eprintln!("{}", error_message(&reason));
+    if let Some(hint) = reason.hint() {
+      eprintln!("{}", auto().bold().cyan().s("hint").reset().s(": ").s(hint));
+    }
+    std::process::exit(reason.exit_code());
+  }
+
+  /// Displays the summary of written files, fails when some files were not overwritten.
+  fn report_writes(writer: &Writer) -> Result<()> {
+    if writer.is_empty() {
+      return Ok(());
+    }
+    print!("{}", writer.summary());
+    for path in writer.adopted() {
+      let verb = if writer.is_dry_run() { "would be" } else { "was" };
+      log::warning(format!("file not generated before {verb} overwritten: {}", path.display()));
+    }
+    let refused = writer.refused().len();
+    let files = if refused == 1 { "file was" } else { "files were" };
+    if refused > 0 && writer.is_dry_run() {
+      log::warning(format!("{refused} {files} not overwritten because of changed content, use --force to overwrite"));
+    } else if refused > 0 {
+      return Err(MaggError::NotOverwritten {
+        paths: writer.refused().into_iter().map(|path| path.to_path_buf()).collect(),
+      });
+    }
+    Ok(())
+  }
+
   //
-  match get_cli_action() {
-    Action::Readme(file_name) => match readme::scaffold_readme(file_name) {
-      Ok(contents) => {
-        utils::write_file("README.md", &contents).unwrap();
+  let (action, globals) = get_cli_action();
+  let (root_dir, dirs) = match globals.root_dir().and_then(|root_dir| {
+    let dirs = manifest::select_packages(&root_dir, globals.workspace, &globals.packages)?;
+    Ok((root_dir, dirs))
+  }) {
+    Ok(selected) => selected,
+    Err(reason) => {
+      fail(reason);
+    }
+  };
+  let record_dir = match &action {
+    Action::New(path, ..) => PathBuf::from(path),
+    _ => globals.record_dir(&root_dir),
+  };
+  let mut writer = Writer::new(globals.force, globals.dry_run, record_dir);
+  match action {
+    Action::New(path, empty, name, authors, description, license, repository, library) => {
+      if let Err(reason) = new_crate(&mut writer, &path, empty, name, authors, description, license, repository, library) {
+        fail(reason);
+      }
+    }
+    Action::Readme(file_names, test_examples) => {
+      let options = file_names.into_iter().fold(readme::Options::new(), |options, file_name| options.body(file_name));
+      for dir in dirs {
+        let output_dir = globals.output_dir(&root_dir, &dir);
+        match Manifest::load(&dir).and_then(|manifest| {
+          let readmes = readme::scaffold_readmes(&manifest, &options.clone().output_dir(&output_dir))?;
+          if test_examples {
+            for (_, contents) in &readmes {
+              if readme::test_examples(&manifest, contents)? == 0 {
+                log::info("no Rust code blocks found in README.md");
+              }
+            }
+          } else {
+            write_files(&mut writer, &output_dir, &readmes)?;
+            for (file_name, contents) in &readmes {
+              for diagnostic in markdown::lint(contents, output_dir.join(file_name), &output_dir) {
+                log::warning(diagnostic);
+              }
+            }
+          }
+          Ok(())
+        }) {
+          Ok(()) => {}
+          Err(reason) => {
+            fail(reason);
+          }
+        }
       }
-      Err(reason) => {
-        eprintln!("{}", error_message(reason));
-        std::process::exit(1);
+    }
+    Action::Licenses(true) => {
+      let mut statuses = vec![];
+      for dir in dirs {
+        let output_dir = globals.output_dir(&root_dir, &dir);
+        match Manifest::load(&dir).and_then(|manifest| licenses::check_license_files(&manifest, &output_dir)) {
+          Ok(package_statuses) => statuses.extend(package_statuses.into_iter().map(|(file_name, status)| (output_dir.join(file_name), status))),
+          Err(reason) => {
+            fail(reason);
+          }
+        }
+      }
+      for (path, status) in &statuses {
+        let message = match status {
+          licenses::Status::Missing => "missing license file",
+          licenses::Status::Extra => "extra license file",
+          licenses::Status::Modified => "modified license file",
+        };
+        println!("{}: {}", message, path.display());
+      }
+      if !statuses.is_empty() {
+        std::process::exit(EXIT_FAILURE);
+      }
+    }
+    Action::Licenses(false) => {
+      for dir in dirs {
+        let output_dir = globals.output_dir(&root_dir, &dir);
+        match Manifest::load(&dir)
+          .and_then(|manifest| licenses::get_license_files(&manifest))
+          .and_then(|files| write_files(&mut writer, &output_dir, &files))
+        {
+          Ok(()) => {}
+          Err(reason) => {
+            fail(reason);
+          }
+        }
+      }
+    }
+    Action::LicensesAudit(allow_missing) => {
+      let mut violations = vec![];
+      let mut unresolved = vec![];
+      for dir in dirs {
+        let output_dir = globals.output_dir(&root_dir, &dir);
+        match Manifest::load(&dir).and_then(|manifest| licenses::audit(&manifest)).and_then(|audit| {
+          // Incomplete notices are written only when explicitly allowed.
+          if audit.unresolved.is_empty() || allow_missing {
+            write_files(&mut writer, &output_dir, &[(licenses::NOTICES_FILE_NAME.to_string(), audit.notices.clone())])?;
+          }
+          Ok(audit)
+        }) {
+          Ok(audit) => {
+            violations.extend(audit.violations);
+            unresolved.extend(audit.unresolved);
+          }
+          Err(reason) => {
+            fail(reason);
+          }
+        }
+      }
+      if allow_missing {
+        for dependency in &unresolved {
+          log::warning(format!("unresolved dependency: {} {}: {}", dependency.name, dependency.version, dependency.reason));
+        }
+      }
+      if !violations.is_empty() || (!unresolved.is_empty() && !allow_missing) {
+        if let Err(reason) = report_writes(&writer) {
+          eprintln!("{}", error_message(&reason));
+        }
+        for violation in violations {
+          println!("license not allowed: {} {}: {}", violation.name, violation.version, violation.license);
+        }
+        if !allow_missing && !unresolved.is_empty() {
+          for dependency in &unresolved {
+            println!("unresolved dependency: {} {}: {}", dependency.name, dependency.version, dependency.reason);
+          }
+          let reason = format!(
+            "{} not generated because licenses of some dependencies could not be resolved, use --allow-missing to generate incomplete notices",
+            licenses::NOTICES_FILE_NAME
+          );
+          eprintln!("{}", error_message(&MaggError::validation(reason)));
+        }
+        std::process::exit(EXIT_FAILURE);
       }
-    },
-    Action::Licenses => {
-      utils::write_file("LICENSE", &get_apache_2()).unwrap();
-      utils::write_file("NOTICE", &get_apache_notice()).unwrap();
-      utils::write_file("LICENSE-MIT", &get_mit()).unwrap();
     }
     Action::CodeOfConduct => {
-      utils::write_file("CODE_OF_CONDUCT.md", &get_code_of_conduct()).unwrap();
+      for dir in dirs {
+        let output_dir = globals.output_dir(&root_dir, &dir);
+        match Manifest::load(&dir)
+          .and_then(|manifest| code_of_conduct::get_code_of_conduct(&manifest))
+          .and_then(|contents| write_files(&mut writer, &output_dir, &[("CODE_OF_CONDUCT.md".to_string(), contents)]))
+        {
+          Ok(()) => {}
+          Err(reason) => {
+            fail(reason);
+          }
+        }
+      }
     }
-    Action::Changelog(start_revision, end_revision, milestone, repository, dir, verbose, exclude_commit, exclude_pr) => {
-      match changelog::get_changelog(verbose, &start_revision, &end_revision, &milestone, &repository, &dir, exclude_commit, exclude_pr) {
+    Action::Community(kind, true) => {
+      let mut statuses = vec![];
+      for dir in dirs {
+        let output_dir = globals.output_dir(&root_dir, &dir);
+        match Manifest::load(&dir).and_then(|manifest| community::get_files(&manifest, kind).and_then(|files| utils::check_files(&output_dir, &files))) {
+          Ok(package_statuses) => statuses.extend(package_statuses.into_iter().map(|(file_name, status)| (output_dir.join(file_name), status))),
+          Err(reason) => {
+            fail(reason);
+          }
+        }
+      }
+      for (path, status) in &statuses {
+        let message = match status {
+          FileStatus::Missing => "missing file",
+          FileStatus::Modified => "modified file",
+        };
+        println!("{}: {}", message, path.display());
+      }
+      if !statuses.is_empty() {
+        std::process::exit(EXIT_FAILURE);
+      }
+    }
+    Action::Community(kind, false) => {
+      for dir in dirs {
+        let output_dir = globals.output_dir(&root_dir, &dir);
+        match Manifest::load(&dir)
+          .and_then(|manifest| community::get_files(&manifest, kind))
+          .and_then(|files| write_files(&mut writer, &output_dir, &files))
+        {
+          Ok(()) => {}
+          Err(reason) => {
+            fail(reason);
+          }
+        }
+      }
+    }
+    Action::Ci(check) => {
+      let mut failed = false;
+      // Workspace members share workflows, which are generated only once.
+      let mut roots = vec![];
+      for dir in dirs {
+        let output_dir = globals.output_dir(&root_dir, &dir);
+        match Manifest::load(&dir).and_then(|manifest| {
+          let workflows_root = globals.output_dir(&root_dir, &ci::workflows_root(&manifest, &root_dir)?);
+          if !roots.contains(&workflows_root) {
+            let workflows = ci::get_workflows(&manifest)?;
+            if check {
+              for (file_name, status) in utils::check_files(&workflows_root, &workflows)? {
+                let message = match status {
+                  FileStatus::Missing => "missing file",
+                  FileStatus::Modified => "modified file",
+                };
+                println!("{}: {}", message, workflows_root.join(file_name).display());
+                failed = true;
+              }
+            } else {
+              write_files(&mut writer, &workflows_root, &workflows)?;
+            }
+            roots.push(workflows_root.clone());
+          }
+          ci::check_badges(&output_dir, &workflows_root)
+        }) {
+          Ok(diagnostics) => {
+            for diagnostic in diagnostics {
+              if check {
+                println!("{diagnostic}");
+                failed = true;
+              } else {
+                log::warning(diagnostic);
+              }
+            }
+          }
+          Err(reason) => {
+            fail(reason);
+          }
+        }
+      }
+      if failed {
+        std::process::exit(EXIT_FAILURE);
+      }
+    }
+    Action::Changelog(ChangelogArgs {
+      start_revision,
+      end_revision,
+      milestone,
+      repository,
+      dir,
+      exclude_commit,
+      exclude_pr,
+      interactive,
+      jobs,
+      no_cache,
+      release_tag,
+      attach,
+      api_url,
+    }) => {
+      // Commits are searched in the directory of the manifest, unless the directory is specified.
+      let dir = dir.unwrap_or_else(|| {
+        if root_dir.as_os_str().is_empty() {
+          ".".to_string()
+        } else {
+          root_dir.to_string_lossy().to_string()
+        }
+      });
+      let selection = if interactive {
+        select_changelog(&dir, start_revision, end_revision, milestone, repository)
+      } else {
+        Ok((
+          start_revision.unwrap_or_default(),
+          end_revision.unwrap_or_default(),
+          milestone.unwrap_or_default(),
+          repository.unwrap_or_default(),
+        ))
+      };
+      let (start_revision, end_revision, milestone, repository) = selection.unwrap_or_else(|reason| fail(reason));
+      let changelog_repository = changelog_repository(&dir, &repository).unwrap_or_else(|reason| fail(reason));
+      let mut options = changelog::Options::new(start_revision, end_revision, milestone, repository).dir(dir).jobs(jobs);
+      if no_cache {
+        options = options.no_cache();
+      }
+      for pattern in exclude_commit {
+        options = options.exclude_commit(pattern);
+      }
+      for pattern in exclude_pr {
+        options = options.exclude_pull_request(pattern);
+      }
+      log::verbose(format!("\nCOMMANDS\n{SEPARATOR_LINE}"));
+      options = options.on_event(|event| match event {
+        changelog::Event::Started { command, current, total } => {
+          log::verbose(command);
+          log::progress(*current, *total, command);
+        }
+        changelog::Event::Finished { command, duration, output } => {
+          log::debug(format!("{command} finished in {duration:.2?} with {} lines of output", output.lines().count()));
+        }
+        changelog::Event::Cached { number } => {
+          log::debug(format!("commits of pull request #{number} taken from cache"));
+        }
+      });
+      match changelog::get_changelog(&options) {
         Ok(changelog) => {
-          println!("\nCHANGELOG");
-          println!("{SEPARATOR_LINE}");
-          println!("{}", changelog)
+          log::clear_progress();
+          log::verbose(changelog.render_details().trim_end());
+          for warning in &changelog.warnings {
+            log::warning(warning);
+          }
+          let changelog = changelog.render(&changelog_repository);
+          for diagnostic in markdown::lint(&changelog, "CHANGELOG", "") {
+            log::warning(diagnostic);
+          }
+          print!("{changelog}");
+          if let Some(tag) = release_tag {
+            if writer.is_dry_run() {
+              // No requests are sent to the forge in dry-run mode, the planned release is reported instead.
+              let kind = if release::is_marked_prerelease(changelog_repository.forge(), &tag) {
+                "prerelease"
+              } else {
+                "release"
+              };
+              log::info(format!("would publish {kind} {tag}: {changelog_repository}"));
+              for path in attach {
+                log::info(format!("would attach: {}", Path::new(&path).file_name().unwrap_or_default().to_string_lossy()));
+              }
+            } else {
+              let mut options = release::Options::new(changelog_repository.to_string(), tag).notes(changelog);
+              for path in attach {
+                options = options.asset(path);
+              }
+              if let Some(api_url) = api_url {
+                options = options.api_url(api_url);
+              }
+              match release::publish(&options) {
+                Ok(release) => {
+                  let action = if release.created { "created" } else { "updated" };
+                  let kind = if release.prerelease { "prerelease" } else { "release" };
+                  log::info(format!("{action} {kind} {}: {}", release.tag, release.url));
+                  for asset in release.assets {
+                    log::info(format!("attached: {asset}"));
+                  }
+                }
+                Err(reason) => fail(reason),
+              }
+            }
+          }
         }
         Err(reason) => {
-          eprintln!("{}", error_message(reason));
-          std::process::exit(1);
+          log::clear_progress();
+          fail(reason);
+        }
+      }
+    }
+    Action::Headers(check) => {
+      let mut changes = vec![];
+      for dir in dirs {
+        match Manifest::load(&dir).and_then(|manifest| headers::process_headers(&manifest)) {
+          Ok(mut package_changes) => changes.append(&mut package_changes),
+          Err(reason) => {
+            fail(reason);
+          }
+        }
+      }
+      if check {
+        for (path, status, _) in &changes {
+          let message = if *status == Status::Missing { "missing header" } else { "outdated header" };
+          println!("{}: {}", message, path.display());
+        }
+        if !changes.is_empty() {
+          std::process::exit(EXIT_FAILURE);
+        }
+      } else {
+        for (path, _, contents) in &changes {
+          match writer.update(path, contents) {
+            Ok((_, Some(diff))) => print!("{diff}"),
+            Ok(_) => {}
+            Err(reason) => fail(reason),
+          }
+        }
+      }
+    }
+    Action::LintMarkdown(file_names) => {
+      let mut diagnostics = vec![];
+      for dir in dirs {
+        for file_name in &file_names {
+          match markdown::lint_file(dir.join(file_name)) {
+            Ok(mut file_diagnostics) => diagnostics.append(&mut file_diagnostics),
+            Err(reason) => {
+              fail(reason);
+            }
+          }
+        }
+      }
+      if !diagnostics.is_empty() {
+        for diagnostic in diagnostics {
+          println!("{diagnostic}");
         }
+        std::process::exit(EXIT_FAILURE);
+      }
+    }
+    Action::LintManifest => {
+      let mut findings = vec![];
+      for dir in dirs {
+        match Manifest::load(&dir).and_then(|manifest| lint::manifest::lint(&manifest)) {
+          Ok(mut package_findings) => findings.append(&mut package_findings),
+          Err(reason) => {
+            fail(reason);
+          }
+        }
+      }
+      for finding in &findings {
+        println!("{finding}");
+      }
+      if findings.iter().any(|finding| finding.severity == Severity::Error) {
+        std::process::exit(EXIT_FAILURE);
+      }
+    }
+    Action::Completions(shell) => {
+      let mut command = get_command();
+      let mut completions = vec![];
+      clap_complete::generate(shell, &mut command, env!("CARGO_PKG_NAME"), &mut completions);
+      let file = (shell.file_name(env!("CARGO_PKG_NAME")), String::from_utf8_lossy(&completions).to_string());
+      if let Err(reason) = write_or_print(&mut writer, globals.output_dir.as_deref(), &[file]) {
+        fail(reason);
+      }
+    }
+    Action::Man => {
+      // Without the output directory, subcommand pages would be printed as one unreadable page.
+      let pages = man_pages().map(|pages| if globals.output_dir.is_some() { pages } else { pages.into_iter().take(1).collect() });
+      if let Err(reason) = pages.and_then(|pages| write_or_print(&mut writer, globals.output_dir.as_deref(), &pages)) {
+        fail(reason);
       }
     }
     Action::Nothing => {
       // No specific action was requested.
     }
   }
+  if let Err(reason) = report_writes(&writer) {
+    fail(reason);
+  }
+}
+
+/// Writes generated files to the package directory, in dry-run mode diffs are displayed instead.
+fn write_files(writer: &mut Writer, dir: &Path, files: &[(String, String)]) -> Result<()> {
+  for (file_name, contents) in files {
+    if let (_, Some(diff)) = writer.write(dir.join(file_name), contents)? {
+      print!("{diff}");
+    }
+  }
+  Ok(())
+}
+
+/// Writes generated files to the output directory, when specified, otherwise prints them.
+fn write_or_print(writer: &mut Writer, output_dir: Option<&str>, files: &[(String, String)]) -> Result<()> {
+  match output_dir {
+    Some(output_dir) => write_files(writer, Path::new(output_dir), files),
+    None => {
+      for (_, contents) in files {
+        print!("{contents}");
+      }
+      Ok(())
+    }
+  }
+}
+
+/// Returns man pages of magg and all its subcommands, like `magg.1` and `magg-lint-markdown.1`.
+fn man_pages() -> Result<Vec<(String, String)>> {
+  fn render(command: &Command, name: String, pages: &mut Vec<(String, String)>) -> Result<()> {
+    let mut page = vec![];
+    clap_mangen::Man::new(command.clone().name(name.clone()))
+      .render(&mut page)
+      .map_err(|e| MaggError::io(format!("failed to render man page {name}"), e))?;
+    pages.push((format!("{name}.1"), String::from_utf8_lossy(&page).to_string()));
+    for subcommand in command.get_subcommands().filter(|subcommand| subcommand.get_name() != "help") {
+      render(subcommand, format!("{name}-{}", subcommand.get_name()), pages)?;
+    }
+    Ok(())
+  }
+  let mut command = get_command();
+  command.build();
+  let mut pages = vec![];
+  render(&command, env!("CARGO_PKG_NAME").to_string(), &mut pages)?;
+  Ok(pages)
+}
+
+/// Returns the repository of the changelog, used in commit links and for publishing the release.
+/// The forge is determined by the Git remote `origin`, which must point to the same repository when the repository is specified.
+fn changelog_repository(dir: &str, repository: &str) -> Result<Repository> {
+  match Repository::from_git_remote(dir) {
+    Ok(remote) if repository.is_empty() || remote.path() == repository => Ok(remote),
+    _ => Err(MaggError::validation(format!(
+      "forge of repository {repository} cannot be determined, Git remote 'origin' does not point to it"
+    ))),
+  }
+}
+
+/// Selects revisions, milestone and repository of the changelog interactively, specified values are offered as defaults.
+/// Returns the start revision, the end revision, the milestone and the repository.
+fn select_changelog(
+  dir: &str,
+  start_revision: Option<String>,
+  end_revision: Option<String>,
+  milestone: Option<String>,
+  repository: Option<String>,
+) -> Result<(String, String, String, String)> {
+  let detected = repository.or_else(|| Repository::from_git_remote(dir).ok().map(|repository| repository.path().to_string()));
+  let repository = prompt::ask("Repository", detected.as_deref())?;
+  let tags = changelog::recent_tags(dir, RECENT_TAGS).unwrap_or_else(|reason| {
+    log::warning(format!("tags not available: {reason}"));
+    vec![]
+  });
+  let start_default = start_revision.or_else(|| tags.first().cloned());
+  let start_revision = prompt::select("Start revision", &tags, start_default.as_deref())?;
+  let end_choices = std::iter::once("HEAD".to_string()).chain(tags).collect::<Vec<String>>();
+  let end_revision = prompt::select("End revision", &end_choices, Some(end_revision.as_deref().unwrap_or("HEAD")))?;
+  let milestones = changelog::recent_milestones(&repository, RECENT_CLOSED_MILESTONES).unwrap_or_else(|reason| {
+    log::warning(format!("milestones not available: {reason}"));
+    vec![]
+  });
+  let milestone_default = milestone.or_else(|| milestones.first().cloned());
+  let milestone = prompt::select("Milestone", &milestones, milestone_default.as_deref())?;
+  for (name, value) in [
+    ("repository", &repository),
+    ("start revision", &start_revision),
+    ("end revision", &end_revision),
+    ("milestone", &milestone),
+  ] {
+    if value.is_empty() {
+      return Err(MaggError::validation(format!("changelog {name} not specified")));
+    }
+  }
+  eprintln!("\nChangelog of {repository}, commits {start_revision}...{end_revision}, milestone {milestone}");
+  if !prompt::confirm("Generate changelog?", true)? {
+    return Err(MaggError::validation("changelog generation cancelled"));
+  }
+  Ok((start_revision, end_revision, milestone, repository))
+}
+
+/// Scaffolds the crate repository, missing properties are asked for in interactive mode.
+#[allow(clippy::too_many_arguments)]
+fn new_crate(
+  writer: &mut Writer,
+  path: &str,
+  empty: bool,
+  name: Option<String>,
+  authors: Vec<String>,
+  description: Option<String>,
+  license: Option<String>,
+  repository: Option<String>,
+  library: bool,
+) -> Result<()> {
+  let dir = Path::new(path);
+  if empty && dir.read_dir().is_ok_and(|mut entries| entries.next().is_some()) {
+    return Err(MaggError::validation(format!("directory {} is not empty, use init command instead", dir.display())));
+  }
+  let dir_name = utils::absolute(dir)?.file_name().map(|name| name.to_string_lossy().to_string());
+  let name = prompt::value_or_ask(name, "Crate name", dir_name)?.ok_or_else(|| MaggError::validation("crate name not specified, use --name"))?;
+  let authors = if authors.is_empty() {
+    let author = prompt::value_or_ask(None, "Author", scaffold::default_author())?;
+    vec![author.ok_or_else(|| MaggError::validation("author not specified, use --author"))?]
+  } else {
+    authors
+  };
+  let description = prompt::value_or_ask(description, "Description", None)?.unwrap_or_default();
+  let license = prompt::value_or_ask(license, "License", Some(scaffold::DEFAULT_LICENSE.to_string()))?.unwrap_or_default();
+  let repository = prompt::value_or_ask(repository, "Repository URL", None)?.ok_or_else(|| MaggError::validation("repository URL not specified, use --repository"))?;
+  let options = scaffold::Options {
+    name,
+    authors,
+    description,
+    license,
+    repository,
+    library,
+  };
+  let (templates, generated) = scaffold::scaffold(dir, &options)?;
+  for (file_name, contents) in templates {
+    if let (_, Some(diff)) = writer.write_template(dir.join(file_name), &contents)? {
+      print!("{diff}");
+    }
+  }
+  write_files(writer, dir, &generated)
 }
 
 /// Matches a mandatory string argument.
@@ -217,6 +1248,11 @@ fn match_string(matches: &ArgMatches, name: &str) -> String {
   matches.get_one::<String>(name).unwrap().trim().to_string()
 }
 
+/// Matches an optional string argument.
+fn match_optional_string(matches: &ArgMatches, name: &str) -> Option<String> {
+  matches.get_one::<String>(name).map(|value| value.trim().to_string())
+}
+
 /// Matches a mandatory boolean argument.
 fn match_boolean(matches: &ArgMatches, name: &str) -> bool {
   matches.get_flag(name)
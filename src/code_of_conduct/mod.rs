@@ -1,8 +1,68 @@
 //! Code of conduct generator
+//!
+//! The code of conduct and the contact for reporting violations are configured in `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.magg.code-of-conduct]
+//! code = "contributor-covenant-2.1" # default
+//! contact = "conduct@example.com"   # e-mail address, URL of a reporting form or any text
+//! ```
+//!
+//! Available codes are `contributor-covenant-2.0`, `contributor-covenant-2.1`,
+//! `contributor-covenant-3.0`, `rust` and `citizen`. When no contact is configured,
+//! the e-mail address of the first package author is used.
 
-const CODE_OF_CONDUCT: &str = include_str!("CODE_OF_CONDUCT");
-const MAIL: &str = "[depta@engos.de](mailto:depta@engos.de)";
+use crate::errors::*;
+use crate::manifest::Manifest;
+use crate::utils;
 
-pub fn get_code_of_conduct() -> String {
-  CODE_OF_CONDUCT.replace("::MAIL::", MAIL)
+/// Identifier of the default code of conduct.
+const DEFAULT_CODE: &str = "contributor-covenant-2.1";
+
+/// Bundled codes of conduct: identifier, text, badge label and badge message.
+const CODES: [(&str, &str, &str, &str); 5] = [
+  ("contributor-covenant-2.0", include_str!("CONTRIBUTOR-COVENANT-2.0"), "Contributor Covenant", "2.0"),
+  ("contributor-covenant-2.1", include_str!("CONTRIBUTOR-COVENANT-2.1"), "Contributor Covenant", "2.1"),
+  ("contributor-covenant-3.0", include_str!("CONTRIBUTOR-COVENANT-3.0"), "Contributor Covenant", "3.0"),
+  ("rust", include_str!("RUST"), "Code of Conduct", "Rust"),
+  ("citizen", include_str!("CITIZEN"), "Code of Conduct", "Citizen"),
+];
+
+/// Code of conduct selected for the package.
+pub struct CodeOfConduct {
+  /// Text of the code of conduct, with placeholders.
+  text: &'static str,
+  /// Label of the badge, like `Contributor Covenant`.
+  pub badge_label: &'static str,
+  /// Message of the badge, like `2.1`.
+  pub badge_message: &'static str,
+}
+
+impl CodeOfConduct {
+  /// Returns the code of conduct configured in package metadata.
+  pub fn resolve(manifest: &Manifest) -> Result<Self> {
+    let code = manifest.metadata_str("code-of-conduct.code")?.unwrap_or(DEFAULT_CODE);
+    let (_, text, badge_label, badge_message) = CODES.iter().find(|(id, _, _, _)| id.eq_ignore_ascii_case(code)).ok_or_else(|| {
+      let ids = CODES.iter().map(|(id, _, _, _)| *id).collect::<Vec<&str>>().join(", ");
+      MaggError::validation(format!("unknown code of conduct: {code}, expected one of: {ids}"))
+    })?;
+    Ok(Self { text, badge_label, badge_message })
+  }
+}
+
+/// Returns the content of the code of conduct file.
+pub fn get_code_of_conduct(manifest: &Manifest) -> Result<String> {
+  let code = CodeOfConduct::resolve(manifest)?;
+  Ok(code.text.replace("::CONTACT::", &contact(manifest)?).replace("::NAME::", manifest.name()?))
+}
+
+/// Returns the contact for reporting violations, formatted as Markdown.
+fn contact(manifest: &Manifest) -> Result<String> {
+  let contact = match manifest.metadata_str("code-of-conduct.contact")? {
+    Some(contact) => contact.trim().to_string(),
+    None => manifest.author_email().ok_or_else(|| {
+      MaggError::manifest("code of conduct contact not found, set package.metadata.magg.code-of-conduct.contact or e-mail address in package.authors in Cargo.toml")
+    })?,
+  };
+  Ok(utils::markdown_contact(&contact))
 }
@@ -0,0 +1,212 @@
+//! # Community health files generator
+//!
+//! Generates contributing guidelines, security policy, support information,
+//! issue templates and the pull request template from bundled templates.
+//! The security contact is configured in `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.magg.security]
+//! contact = "security@example.com" # e-mail address, URL of a reporting form or any text
+//! ```
+//!
+//! When no contact is configured, the e-mail address of the first package author is used.
+//!
+//! Issue templates are placed where the forge hosting the repository looks for them:
+//! issue forms in `.github/ISSUE_TEMPLATE` for GitHub, in `.gitea/ISSUE_TEMPLATE` for Gitea,
+//! and Markdown description templates in `.gitlab/issue_templates` for GitLab.
+
+use crate::errors::*;
+use crate::manifest::Manifest;
+use crate::repository::{Forge, Repository};
+use crate::utils;
+use std::collections::BTreeSet;
+
+/// Community health files generated by a single command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kind {
+  /// Contributing guidelines.
+  Contributing,
+  /// Security policy.
+  Security,
+  /// Support information.
+  Support,
+  /// Issue templates and the pull request template.
+  IssueTemplates,
+}
+
+/// Version with major, minor and patch numbers.
+type Version = (u64, u64, u64);
+
+/// Function returning the value of the placeholder.
+type Value = fn(&Manifest) -> Result<String>;
+
+/// Placeholders in templates and functions returning their values,
+/// values are evaluated only for placeholders present in the template.
+const PLACEHOLDERS: [(&str, Value); 8] = [
+  ("::NAME::", |manifest| Ok(manifest.name()?.to_string())),
+  ("::VERSION::", |manifest| Ok(version(manifest)?.to_string())),
+  ("::LICENSE::", |manifest| {
+    Ok(
+      manifest
+        .package_str("license")?
+        .ok_or_else(|| MaggError::manifest("package.license not found in Cargo.toml"))?
+        .to_string(),
+    )
+  }),
+  ("::REPOSITORY::", |manifest| Ok(Repository::resolve(manifest)?.to_string())),
+  ("::ISSUES::", |manifest| Ok(Repository::resolve(manifest)?.issues_url())),
+  ("::SECURITY_URL::", |manifest| Ok(Repository::resolve(manifest)?.blob_url("SECURITY.md"))),
+  ("::CONTACT::", security_contact),
+  ("::SUPPORTED_VERSIONS::", supported_versions),
+];
+
+/// Returns the names and contents of community health files of the specified kind.
+pub fn get_files(manifest: &Manifest, kind: Kind) -> Result<Vec<(String, String)>> {
+  let templates: &[(&str, &str)] = match kind {
+    Kind::Contributing => &[("CONTRIBUTING.md", include_str!("CONTRIBUTING"))],
+    Kind::Security => &[("SECURITY.md", include_str!("SECURITY"))],
+    Kind::Support => &[("SUPPORT.md", include_str!("SUPPORT"))],
+    Kind::IssueTemplates => match Repository::resolve(manifest)?.forge() {
+      Forge::GitHub => &[
+        (".github/ISSUE_TEMPLATE/bug_report.yml", include_str!("BUG_REPORT")),
+        (".github/ISSUE_TEMPLATE/feature_request.yml", include_str!("FEATURE_REQUEST")),
+        (".github/ISSUE_TEMPLATE/config.yml", include_str!("ISSUE_CONFIG")),
+        (".github/pull_request_template.md", include_str!("PULL_REQUEST_TEMPLATE")),
+      ],
+      Forge::GitLab => &[
+        (".gitlab/issue_templates/Bug.md", include_str!("BUG_REPORT_GITLAB")),
+        (".gitlab/issue_templates/Feature.md", include_str!("FEATURE_REQUEST_GITLAB")),
+        (".gitlab/merge_request_templates/Default.md", include_str!("PULL_REQUEST_TEMPLATE")),
+      ],
+      Forge::Gitea => &[
+        (".gitea/ISSUE_TEMPLATE/bug_report.yml", include_str!("BUG_REPORT")),
+        (".gitea/ISSUE_TEMPLATE/feature_request.yml", include_str!("FEATURE_REQUEST")),
+        (".gitea/ISSUE_TEMPLATE/config.yml", include_str!("ISSUE_CONFIG")),
+        (".gitea/pull_request_template.md", include_str!("PULL_REQUEST_TEMPLATE")),
+      ],
+    },
+  };
+  templates
+    .iter()
+    .map(|(file_name, template)| Ok((file_name.to_string(), substitute(manifest, template)?)))
+    .collect()
+}
+
+/// Replaces placeholders in the template.
+fn substitute(manifest: &Manifest, template: &str) -> Result<String> {
+  let mut content = template.to_string();
+  for (placeholder, value) in PLACEHOLDERS {
+    if content.contains(placeholder) {
+      content = content.replace(placeholder, &value(manifest)?);
+    }
+  }
+  Ok(content)
+}
+
+/// Returns the version of the package.
+fn version(manifest: &Manifest) -> Result<&str> {
+  manifest
+    .package_str("version")?
+    .ok_or_else(|| MaggError::manifest("package.version not found in Cargo.toml"))
+}
+
+/// Returns the contact for reporting vulnerabilities, formatted as Markdown.
+fn security_contact(manifest: &Manifest) -> Result<String> {
+  let contact = match manifest.metadata_str("security.contact")? {
+    Some(contact) => contact.trim().to_string(),
+    None => manifest
+      .author_email()
+      .ok_or_else(|| MaggError::manifest("security contact not found, set package.metadata.magg.security.contact or e-mail address in package.authors in Cargo.toml"))?,
+  };
+  Ok(utils::markdown_contact(&contact))
+}
+
+/// Returns the rows of the supported versions table, derived from the released versions of the package.
+/// Released versions are read from Git tags, like `v1.2.3` or `name-v1.2.3`, pre-releases are skipped.
+/// Only the latest release series receives security updates: the latest major version,
+/// or the latest minor version for versions `0.x`. Without release tags,
+/// the table is derived from the current version of the package.
+fn supported_versions(manifest: &Manifest) -> Result<String> {
+  let version = version(manifest)?;
+  let current = parse_version(version).ok_or_else(|| MaggError::manifest(format!("invalid package version: {version}")))?;
+  let mut released = released_versions(manifest)?.into_iter().map(release_series).collect::<BTreeSet<Version>>();
+  if released.is_empty() {
+    let supported = release_series(current);
+    let mut rows = format!("| {} | :white_check_mark: |", series_name(supported));
+    let unsupported = match supported {
+      (0, 0, patch) => (patch > 0).then(|| format!("< 0.0.{patch}")),
+      (0, minor, _) => Some(format!("< 0.{minor}")),
+      (major, _, _) => Some(format!("< {major}.0")),
+    };
+    if let Some(unsupported) = unsupported {
+      rows.push_str(&format!("\n| {unsupported} | :x: |"));
+    }
+    return Ok(rows);
+  }
+  released.insert(release_series(current));
+  Ok(
+    released
+      .iter()
+      .rev()
+      .enumerate()
+      .map(|(index, series)| format!("| {} | {} |", series_name(*series), if index == 0 { ":white_check_mark:" } else { ":x:" }))
+      .collect::<Vec<String>>()
+      .join("\n"),
+  )
+}
+
+/// Returns released versions of the package read from Git tags,
+/// no versions are returned when the package is not under version control.
+fn released_versions(manifest: &Manifest) -> Result<Vec<Version>> {
+  let Ok(output) = utils::git(manifest.dir(), &["tag", "--list"]) else {
+    return Ok(vec![]);
+  };
+  let name = manifest.name()?;
+  Ok(
+    output
+      .lines()
+      .map(|tag| tag.trim())
+      .map(|tag| {
+        [format!("{name}-"), format!("{name}@"), format!("{name}/")]
+          .iter()
+          .find_map(|prefix| tag.strip_prefix(prefix.as_str()))
+          .unwrap_or(tag)
+      })
+      .map(|tag| tag.strip_prefix('v').unwrap_or(tag))
+      .filter(|version| !version.contains('-'))
+      .filter_map(parse_version)
+      .collect(),
+  )
+}
+
+/// Parses the version, pre-release and build metadata are ignored.
+fn parse_version(version: &str) -> Option<Version> {
+  let parts = version
+    .split(['-', '+'])
+    .next()
+    .unwrap_or_default()
+    .split('.')
+    .map(|part| part.parse::<u64>())
+    .collect::<Result<Vec<u64>, _>>()
+    .ok()
+    .filter(|parts| parts.len() == 3)?;
+  Some((parts[0], parts[1], parts[2]))
+}
+
+/// Returns the first version of the release series containing the version.
+fn release_series(version: Version) -> Version {
+  match version {
+    (0, 0, patch) => (0, 0, patch),
+    (0, minor, _) => (0, minor, 0),
+    (major, _, _) => (major, 0, 0),
+  }
+}
+
+/// Returns the name of the release series, like `1.x` or `0.2.x`.
+fn series_name(series: Version) -> String {
+  match series {
+    (0, 0, patch) => format!("0.0.{patch}"),
+    (0, minor, _) => format!("0.{minor}.x"),
+    (major, _, _) => format!("{major}.x"),
+  }
+}
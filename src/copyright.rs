@@ -0,0 +1,142 @@
+//! # Copyright years and holders
+//!
+//! The start year is taken from the first commit touching the package, the end year
+//! from the latest one. Holders are taken from `package.authors`, and when no authors
+//! are specified, from the list of Git authors whose share of commits reaches the threshold.
+//! Every value can be overridden in `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.magg.copyright]
+//! start-year = 2015
+//! end-year = 2026
+//! holders = ["Dariusz Depta"]
+//! author-threshold = 10 # percent of commits, used when holders are taken from Git
+//! ```
+
+use crate::errors::*;
+use crate::manifest::Manifest;
+use crate::utils;
+use std::collections::BTreeMap;
+use time::OffsetDateTime;
+
+/// Default minimal share of commits (in percent) of a Git author to become a copyright holder.
+const DEFAULT_AUTHOR_THRESHOLD: i64 = 10;
+
+/// Copyright years and holders.
+#[derive(Clone)]
+pub struct Copyright {
+  /// The first year of the copyright.
+  pub start_year: String,
+  /// The last year of the copyright.
+  pub end_year: String,
+  /// Copyright holders, comma separated.
+  pub holders: String,
+}
+
+impl Copyright {
+  /// Resolves the copyright of the package.
+  pub fn resolve(manifest: &Manifest) -> Result<Self> {
+    let start_year = match year_setting(manifest, "copyright.start-year")? {
+      Some(year) => year,
+      None => commit_year(manifest, &["log", "--reverse", "--format=%ad", "--date=format:%Y", "--", "."]),
+    };
+    let end_year = match year_setting(manifest, "copyright.end-year")? {
+      Some(year) => year,
+      None => commit_year(manifest, &["log", "-1", "--format=%ad", "--date=format:%Y", "--", "."]),
+    };
+    let holders = match holders_setting(manifest)? {
+      Some(holders) => holders,
+      None => match authors(manifest)? {
+        Some(authors) => authors,
+        None => git_authors(manifest)?,
+      },
+    };
+    if holders.is_empty() {
+      return Err(MaggError::manifest(
+        "copyright holders not found, set package.authors or package.metadata.magg.copyright.holders in Cargo.toml",
+      ));
+    }
+    Ok(Self {
+      start_year,
+      end_year,
+      holders: holders.join(", "),
+    })
+  }
+
+  /// Returns the copyright years, like `2015-2026`, or a single year when both years are equal.
+  pub fn years(&self) -> String {
+    if self.start_year == self.end_year {
+      self.start_year.clone()
+    } else {
+      format!("{}-{}", self.start_year, self.end_year)
+    }
+  }
+}
+
+/// Returns the year configured in package metadata.
+fn year_setting(manifest: &Manifest, key: &str) -> Result<Option<String>> {
+  match manifest.metadata(key) {
+    None => Ok(None),
+    Some(toml::Value::Integer(year)) => Ok(Some(year.to_string())),
+    Some(toml::Value::String(year)) => Ok(Some(year.clone())),
+    Some(_) => Err(MaggError::manifest(format!("metadata.magg.{key} in Cargo.toml must be a year"))),
+  }
+}
+
+/// Returns copyright holders configured in package metadata.
+fn holders_setting(manifest: &Manifest) -> Result<Option<Vec<String>>> {
+  match manifest.metadata("copyright.holders") {
+    None => Ok(None),
+    Some(toml::Value::String(holder)) => Ok(Some(vec![holder.clone()])),
+    Some(toml::Value::Array(holders)) => Ok(Some(holders.iter().filter_map(|holder| holder.as_str()).map(|holder| holder.to_string()).collect())),
+    Some(_) => Err(MaggError::manifest("metadata.magg.copyright.holders in Cargo.toml must be a string or an array of strings")),
+  }
+}
+
+/// Returns names of package authors, without e-mail addresses.
+fn authors(manifest: &Manifest) -> Result<Option<Vec<String>>> {
+  match manifest.package("authors") {
+    None => Ok(None),
+    Some(toml::Value::Array(authors)) if !authors.is_empty() => Ok(Some(
+      authors
+        .iter()
+        .filter_map(|author| author.as_str())
+        .map(|author| author.split('<').next().unwrap_or_default().trim().to_string())
+        .filter(|author| !author.is_empty())
+        .collect(),
+    )),
+    Some(toml::Value::Array(_)) => Ok(None),
+    Some(_) => Err(MaggError::manifest("package.authors in Cargo.toml must be an array of strings")),
+  }
+}
+
+/// Returns names of Git authors whose share of commits reaches the configured threshold,
+/// ordered by the number of commits.
+fn git_authors(manifest: &Manifest) -> Result<Vec<String>> {
+  let threshold = match manifest.metadata("copyright.author-threshold") {
+    None => DEFAULT_AUTHOR_THRESHOLD,
+    Some(toml::Value::Integer(threshold)) => *threshold,
+    Some(_) => return Err(MaggError::manifest("metadata.magg.copyright.author-threshold in Cargo.toml must be an integer")),
+  };
+  let Ok(output) = utils::git(manifest.dir(), &["log", "--format=%an", "--", "."]) else {
+    return Ok(vec![]);
+  };
+  let mut counts = BTreeMap::<&str, i64>::new();
+  for author in output.lines().map(|line| line.trim()).filter(|line| !line.is_empty()) {
+    *counts.entry(author).or_default() += 1;
+  }
+  let total = counts.values().sum::<i64>();
+  let mut authors = counts.into_iter().filter(|(_, count)| count * 100 >= threshold * total).collect::<Vec<(&str, i64)>>();
+  authors.sort_by(|(_, a), (_, b)| b.cmp(a));
+  Ok(authors.into_iter().map(|(author, _)| author.to_string()).collect())
+}
+
+/// Returns the year of the commit selected by Git arguments, or the current year
+/// when the package is not under version control or has no commits yet.
+fn commit_year(manifest: &Manifest, args: &[&str]) -> String {
+  utils::git(manifest.dir(), args)
+    .ok()
+    .and_then(|output| output.lines().next().map(|line| line.trim().to_string()))
+    .filter(|year| !year.is_empty())
+    .unwrap_or_else(|| OffsetDateTime::now_utc().year().to_string())
+}
@@ -1,46 +1,255 @@
 //! # Definition of result and errors
+//!
+//! Every error has a kind that determines the exit code of the process,
+//! so scripts calling magg can react to specific failures.
 
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::process::ExitStatus;
 
 /// Common result type.
 pub type Result<T, E = MaggError> = std::result::Result<T, E>;
 
+/// Underlying error that caused the [MaggError].
+pub type Source = Box<dyn std::error::Error + Send + Sync>;
+
+/// Exit code reported when the check or lint found problems.
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code reported by the argument parser when command-line arguments are invalid.
+pub const EXIT_USAGE: i32 = 2;
+/// Exit code of [MaggError::Io].
+pub const EXIT_IO: i32 = 3;
+/// Exit code of [MaggError::Manifest].
+pub const EXIT_MANIFEST: i32 = 4;
+/// Exit code of [MaggError::CommandSpawn].
+pub const EXIT_COMMAND_SPAWN: i32 = 5;
+/// Exit code of [MaggError::CommandExit].
+pub const EXIT_COMMAND_EXIT: i32 = 6;
+/// Exit code of [MaggError::Parse].
+pub const EXIT_PARSE: i32 = 7;
+/// Exit code of [MaggError::Validation].
+pub const EXIT_VALIDATION: i32 = 8;
+/// Exit code of [MaggError::ForgeApi].
+pub const EXIT_FORGE_API: i32 = 9;
+/// Exit code of [MaggError::NotOverwritten].
+pub const EXIT_NOT_OVERWRITTEN: i32 = 10;
+
+/// Exit codes of all error kinds with their descriptions, displayed in the help.
+pub const EXIT_CODES: [(i32, &str); 10] = [
+  (EXIT_FAILURE, "check or lint found problems"),
+  (EXIT_USAGE, "invalid command-line arguments"),
+  (EXIT_IO, "reading or writing files failed"),
+  (EXIT_MANIFEST, "invalid or incomplete Cargo.toml"),
+  (EXIT_COMMAND_SPAWN, "required command (git, gh, cargo) could not be started"),
+  (EXIT_COMMAND_EXIT, "command exited with failure"),
+  (EXIT_PARSE, "invalid input could not be parsed"),
+  (EXIT_VALIDATION, "invalid option or configuration value"),
+  (EXIT_FORGE_API, "forge (GitHub, GitLab, Gitea) API request failed"),
+  (EXIT_NOT_OVERWRITTEN, "files were not overwritten because of changed content"),
+];
+
 /// Error definition.
-#[derive(Debug, PartialEq, Eq)]
-pub struct MaggError(String);
+#[derive(Debug)]
+pub enum MaggError {
+  /// Reading or writing files failed.
+  Io {
+    /// Description of the failed operation.
+    message: String,
+    /// Underlying I/O error.
+    source: io::Error,
+  },
+  /// Cargo manifest is invalid or incomplete.
+  Manifest {
+    /// Description of the problem.
+    message: String,
+    /// Underlying error.
+    source: Option<Source>,
+  },
+  /// Command could not be started.
+  CommandSpawn {
+    /// Name of the program.
+    program: String,
+    /// Underlying I/O error.
+    source: io::Error,
+  },
+  /// Command exited with failure.
+  CommandExit {
+    /// Name of the program.
+    program: String,
+    /// Exit status of the command.
+    status: ExitStatus,
+    /// Standard output of the command.
+    stdout: String,
+    /// Standard error of the command.
+    stderr: String,
+  },
+  /// Input could not be parsed.
+  Parse {
+    /// Description of the problem.
+    message: String,
+    /// Underlying error.
+    source: Option<Source>,
+  },
+  /// Option or configuration value is invalid.
+  Validation {
+    /// Description of the problem.
+    message: String,
+  },
+  /// Forge API request failed.
+  ForgeApi {
+    /// Description of the failed request.
+    message: String,
+    /// Underlying error.
+    source: Option<Source>,
+  },
+  /// Files with changed content were not overwritten.
+  NotOverwritten {
+    /// Paths of files that were not overwritten.
+    paths: Vec<PathBuf>,
+  },
+}
 
 impl std::fmt::Display for MaggError {
   /// Implementation of [Display](std::fmt::Display) trait for [MaggError].
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{}", self.0)
+    match self {
+      Self::Io { message, source } => write!(f, "{message}, reason: {source}"),
+      Self::CommandSpawn { program, source } => write!(f, "failed to spawn command: {program}, reason: {source}"),
+      Self::CommandExit { program, status, stdout, stderr } => {
+        write!(f, "failed to execute command: {program}, status {status}\nstdout:\n{stdout}\nstderr:\n{stderr}\n")
+      }
+      Self::Manifest { message, source } | Self::Parse { message, source } | Self::ForgeApi { message, source } => match source {
+        Some(source) => write!(f, "{message}, reason: {source}"),
+        None => write!(f, "{message}"),
+      },
+      Self::Validation { message } => write!(f, "{message}"),
+      Self::NotOverwritten { paths } => {
+        let files = if paths.len() == 1 { "file was" } else { "files were" };
+        write!(
+          f,
+          "{} {files} not overwritten because of changed content, use --force to overwrite or --dry-run to display changes",
+          paths.len()
+        )
+      }
+    }
+  }
+}
+
+impl std::error::Error for MaggError {
+  /// Returns the underlying error.
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Self::Io { source, .. } | Self::CommandSpawn { source, .. } => Some(source),
+      Self::Manifest { source, .. } | Self::Parse { source, .. } | Self::ForgeApi { source, .. } => source.as_deref().map(|source| source as _),
+      Self::CommandExit { .. } | Self::Validation { .. } | Self::NotOverwritten { .. } => None,
+    }
   }
 }
 
 impl MaggError {
-  /// Creates a new [MaggError] with specified error message.
-  pub fn new(message: impl AsRef<str>) -> Self {
-    Self(message.as_ref().to_string())
+  /// Creates an error of failed reading or writing files.
+  pub fn io(message: impl AsRef<str>, source: io::Error) -> Self {
+    Self::Io {
+      message: message.as_ref().to_string(),
+      source,
+    }
+  }
+
+  /// Creates an error of invalid or incomplete Cargo manifest.
+  pub fn manifest(message: impl AsRef<str>) -> Self {
+    Self::Manifest {
+      message: message.as_ref().to_string(),
+      source: None,
+    }
+  }
+
+  /// Creates an error of input that could not be parsed.
+  pub fn parse(message: impl AsRef<str>) -> Self {
+    Self::Parse {
+      message: message.as_ref().to_string(),
+      source: None,
+    }
+  }
+
+  /// Creates an error of invalid option or configuration value.
+  pub fn validation(message: impl AsRef<str>) -> Self {
+    Self::Validation {
+      message: message.as_ref().to_string(),
+    }
+  }
+
+  /// Creates an error of failed forge API request.
+  pub fn forge(message: impl AsRef<str>) -> Self {
+    Self::ForgeApi {
+      message: message.as_ref().to_string(),
+      source: None,
+    }
+  }
+
+  /// Sets the underlying error of manifest, parse and forge API errors, other errors are returned unchanged.
+  pub fn with_source(mut self, error: impl Into<Source>) -> Self {
+    if let Self::Manifest { source, .. } | Self::Parse { source, .. } | Self::ForgeApi { source, .. } = &mut self {
+      *source = Some(error.into());
+    }
+    self
+  }
+
+  /// Returns the exit code of the process terminated because of this error.
+  pub fn exit_code(&self) -> i32 {
+    match self {
+      Self::Io { .. } => EXIT_IO,
+      Self::Manifest { .. } => EXIT_MANIFEST,
+      Self::CommandSpawn { .. } => EXIT_COMMAND_SPAWN,
+      Self::CommandExit { .. } => EXIT_COMMAND_EXIT,
+      Self::Parse { .. } => EXIT_PARSE,
+      Self::Validation { .. } => EXIT_VALIDATION,
+      Self::ForgeApi { .. } => EXIT_FORGE_API,
+      Self::NotOverwritten { .. } => EXIT_NOT_OVERWRITTEN,
+    }
+  }
+
+  /// Returns the hint how to resolve this error, when available.
+  pub fn hint(&self) -> Option<&'static str> {
+    match self {
+      Self::CommandSpawn { program, source } if source.kind() == io::ErrorKind::NotFound => match program.as_str() {
+        "gh" => Some("install the GitHub CLI from https://cli.github.com and authenticate with `gh auth login`"),
+        "git" => Some("install Git from https://git-scm.com"),
+        "cargo" => Some("install Rust toolchain from https://rustup.rs"),
+        _ => None,
+      },
+      Self::Manifest { source: Some(source), .. } if source.downcast_ref::<io::Error>().is_some_and(|e| e.kind() == io::ErrorKind::NotFound) => {
+        Some("run magg in the package directory or use --manifest-path")
+      }
+      Self::ForgeApi { .. } => Some("check the authentication with `gh auth status` or the GITHUB_TOKEN, GITLAB_TOKEN or GITEA_TOKEN variable, and the repository name"),
+      _ => None,
+    }
   }
 }
 
-pub fn error_read_file(file_name: impl AsRef<Path>, reason: impl ToString) -> MaggError {
-  MaggError::new(format!("failed to read file: {}, with reason: {}", file_name.as_ref().display(), reason.to_string()))
+/// Creates an error of failed reading the file.
+pub(crate) fn error_read_file(file_name: impl AsRef<Path>, source: io::Error) -> MaggError {
+  MaggError::io(format!("failed to read file: {}", file_name.as_ref().display()), source)
 }
 
-pub fn error_spawn_command(program: impl AsRef<str>, reason: impl AsRef<str>) -> MaggError {
-  MaggError::new(format!("failed to spawn command: {}, with reason: {}", program.as_ref(), reason.as_ref()))
+/// Creates an error of command that could not be started.
+pub(crate) fn error_spawn_command(program: impl AsRef<str>, source: io::Error) -> MaggError {
+  MaggError::CommandSpawn {
+    program: program.as_ref().to_string(),
+    source,
+  }
 }
 
-pub fn error_obtain_output(reason: impl AsRef<str>) -> MaggError {
-  MaggError::new(format!("failed to obtain command output with reason: {}", reason.as_ref()))
+/// Creates an error of failed obtaining the output of the command.
+pub(crate) fn error_obtain_output(program: impl AsRef<str>, source: io::Error) -> MaggError {
+  MaggError::io(format!("failed to obtain output of command: {}", program.as_ref()), source)
 }
 
-pub fn error_execute_command(status: ExitStatus, stdout: impl AsRef<str>, stderr: impl AsRef<str>) -> MaggError {
-  MaggError::new(format!(
-    "failed to execute command, status {}\nstdout:\n{}\nstderr:\n{}\n",
+/// Creates an error of command that exited with failure.
+pub(crate) fn error_execute_command(program: impl AsRef<str>, status: ExitStatus, stdout: impl AsRef<str>, stderr: impl AsRef<str>) -> MaggError {
+  MaggError::CommandExit {
+    program: program.as_ref().to_string(),
     status,
-    stdout.as_ref(),
-    stderr.as_ref()
-  ))
+    stdout: stdout.as_ref().to_string(),
+    stderr: stderr.as_ref().to_string(),
+  }
 }
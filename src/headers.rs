@@ -0,0 +1,172 @@
+//! # Source file license headers
+//!
+//! Adds, updates and checks SPDX license headers at the top of source files:
+//!
+//! ```text
+//! // SPDX-License-Identifier: MIT OR Apache-2.0
+//! // Copyright (c) 2015-2026 Dariusz Depta
+//! ```
+//!
+//! Rust files use `//` comments, TOML, YAML and shell files use `#` comments.
+//! Files ignored by `.gitignore`, files of nested packages (directories containing
+//! their own `Cargo.toml`) and generated files (containing `@generated`
+//! or `DO NOT EDIT` near the top) are skipped. Shebang lines, line endings and missing
+//! trailing newlines are preserved.
+//! When a header is updated, the start year of the existing copyright is kept.
+
+use crate::copyright::Copyright;
+use crate::errors::*;
+use crate::manifest::{MANIFEST_FILE_NAME, Manifest};
+use crate::utils::read_file;
+use regex::Regex;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Pattern for matching the start year in the copyright line.
+const COPYRIGHT_YEAR_PATTERN: &str = r#"Copyright \(c\) (\d{4})"#;
+
+/// Regular expression for matching the start year in the copyright line.
+static RE_COPYRIGHT_YEAR: LazyLock<Regex> = LazyLock::new(|| Regex::new(COPYRIGHT_YEAR_PATTERN).unwrap());
+
+/// Markers of generated files.
+const GENERATED_MARKERS: [&str; 2] = ["@generated", "DO NOT EDIT"];
+
+/// Number of leading lines searched for markers of generated files.
+const GENERATED_MARKER_LINES: usize = 5;
+
+/// Status of the license header in a source file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Status {
+  /// The header is missing.
+  Missing,
+  /// The header differs from the expected one.
+  Outdated,
+  /// The header is up to date.
+  Current,
+}
+
+/// Checks license headers in all source files of the package, files are not modified.
+/// Returns the paths, statuses and updated contents of source files having missing or outdated headers.
+pub fn process_headers(manifest: &Manifest) -> Result<Vec<(PathBuf, Status, String)>> {
+  let license = manifest
+    .package_str("license")?
+    .ok_or_else(|| MaggError::manifest("package.license not found in Cargo.toml"))?;
+  let copyright = Copyright::resolve(manifest)?;
+  let mut changes = vec![];
+  for path in source_files(manifest.dir())? {
+    let Some(comment) = comment_prefix(&path) else {
+      continue;
+    };
+    let content = read_file(&path)?;
+    if is_generated(&content) {
+      continue;
+    }
+    let (status, updated) = apply_header(&content, comment, license, &copyright);
+    if status != Status::Current {
+      changes.push((path, status, updated));
+    }
+  }
+  Ok(changes)
+}
+
+/// Returns the paths of files in the directory, respecting `.gitignore` files.
+/// Nested packages have their own licenses, so directories containing `Cargo.toml` are not descended into.
+fn source_files(dir: &Path) -> Result<Vec<PathBuf>> {
+  let root = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+  let mut paths = vec![];
+  let walker = ignore::WalkBuilder::new(root)
+    .hidden(false)
+    .require_git(false)
+    .filter_entry(|entry| {
+      let nested_package = entry.depth() > 0 && entry.file_type().is_some_and(|file_type| file_type.is_dir()) && entry.path().join(MANIFEST_FILE_NAME).is_file();
+      !nested_package && !matches!(entry.file_name().to_str(), Some(".git" | "target"))
+    })
+    .build();
+  for entry in walker {
+    let entry = entry.map_err(|e| MaggError::io(format!("failed to list files in {}", root.display()), io::Error::other(e)))?;
+    if entry.file_type().is_some_and(|file_type| file_type.is_file()) {
+      let path = entry.path();
+      paths.push(path.strip_prefix(".").map(|path| dir.join(path)).unwrap_or(path.to_path_buf()));
+    }
+  }
+  paths.sort();
+  Ok(paths)
+}
+
+/// Returns the line comment prefix for the file, or `None` when the file type is not supported.
+fn comment_prefix(path: &Path) -> Option<&'static str> {
+  match path.extension()?.to_str()? {
+    "rs" => Some("//"),
+    "toml" | "yml" | "yaml" | "sh" | "bash" => Some("#"),
+    _ => None,
+  }
+}
+
+/// Returns `true` when the file content is marked as generated.
+fn is_generated(content: &str) -> bool {
+  content
+    .lines()
+    .take(GENERATED_MARKER_LINES)
+    .any(|line| GENERATED_MARKERS.iter().any(|marker| line.contains(marker)))
+}
+
+/// Returns the status of the header and the content with the expected header.
+/// Line endings and the presence of the trailing newline are preserved, so they never make the header outdated.
+fn apply_header(content: &str, comment: &str, license: &str, copyright: &Copyright) -> (Status, String) {
+  let crlf = content.contains("\r\n");
+  let trailing_newline = content.is_empty() || content.ends_with('\n');
+  let mut lines = content.lines().collect::<Vec<&str>>();
+  // Keep the shebang line at the top, inner attributes like `#![no_std]` are not shebangs.
+  let shebang = if lines.first().is_some_and(|line| line.starts_with("#!") && !line.starts_with("#![")) {
+    Some(lines.remove(0))
+  } else {
+    None
+  };
+  // Remove the existing header, remembering the start year of the copyright.
+  let spdx_prefix = format!("{comment} SPDX-License-Identifier:");
+  let copyright_prefix = format!("{comment} Copyright");
+  let mut start_year = copyright.start_year.clone();
+  let existing = lines.first().is_some_and(|line| line.starts_with(&spdx_prefix));
+  if existing {
+    lines.remove(0);
+    if lines.first().is_some_and(|line| line.starts_with(&copyright_prefix)) {
+      if let Some(captures) = RE_COPYRIGHT_YEAR.captures(lines[0]) {
+        start_year = captures[1].to_string();
+      }
+      lines.remove(0);
+    }
+    if lines.first().is_some_and(|line| line.is_empty()) {
+      lines.remove(0);
+    }
+  }
+  let years = Copyright { start_year, ..copyright.clone() }.years();
+  let mut updated = String::with_capacity(content.len() + 128);
+  if let Some(shebang) = shebang {
+    updated.push_str(shebang);
+    updated.push('\n');
+  }
+  updated.push_str(&format!("{spdx_prefix} {license}\n"));
+  updated.push_str(&format!("{copyright_prefix} (c) {years} {}\n", copyright.holders));
+  if !lines.is_empty() {
+    updated.push('\n');
+    for line in lines {
+      updated.push_str(line);
+      updated.push('\n');
+    }
+  }
+  if !trailing_newline {
+    updated.pop();
+  }
+  if crlf {
+    updated = updated.replace('\n', "\r\n");
+  }
+  let status = if updated == content {
+    Status::Current
+  } else if existing {
+    Status::Outdated
+  } else {
+    Status::Missing
+  };
+  (status, updated)
+}
@@ -1,11 +1,23 @@
 #![doc = include_str!("../docs/README.md")]
 
-mod changelog;
+pub mod changelog;
+mod ci;
 mod cli;
-mod code_of_conduct;
-mod errors;
-mod licenses;
-mod readme;
+pub mod code_of_conduct;
+mod community;
+mod copyright;
+pub mod errors;
+mod headers;
+pub mod licenses;
+mod lint;
+mod log;
+pub mod manifest;
+mod prompt;
+pub mod readme;
+pub mod release;
+mod repository;
+mod scaffold;
 mod utils;
+pub mod writer;
 
 pub use cli::do_action;
@@ -0,0 +1,336 @@
+//! # Dependency license audit
+//!
+//! Collects licenses of all third-party dependencies listed in `Cargo.lock`, without network access.
+//! Manifests and license files of dependencies are read from the vendor directory
+//! (created by `cargo vendor`) or from the local Cargo registry (populated by `cargo fetch`).
+//! Dependencies are checked against the policy of allowed licenses and the notices
+//! with full license texts, grouped by license, are generated:
+//!
+//! ```toml
+//! [package.metadata.magg.licenses]
+//! allowed = ["MIT", "Apache-2.0", "BSD-3-Clause", "Unicode-3.0"]
+//! vendor = "vendor" # default
+//! ```
+//!
+//! When no policy is configured, all licenses are allowed. Dependencies licensed
+//! with `license-file` instead of an SPDX expression can not be verified
+//! and violate any configured policy.
+//!
+//! Dependencies whose licenses could not be collected, like dependencies with sources
+//! not available locally, are reported as unresolved, because the notices do not cover them.
+
+use super::spdx::Expression;
+use super::{EXCEPTIONS, LICENSES, base_id, find};
+use crate::errors::*;
+use crate::manifest::{MANIFEST_FILE_NAME, Manifest, Workspace};
+use crate::utils::{parse_toml, read_file};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Name of the generated notices file.
+pub const NOTICES_FILE_NAME: &str = "THIRD-PARTY-NOTICES";
+
+/// Name of the lock file.
+const LOCK_FILE_NAME: &str = "Cargo.lock";
+
+/// Default vendor directory, relative to the directory of the lock file.
+const DEFAULT_VENDOR_DIR: &str = "vendor";
+
+/// Separator of license groups in the notices file.
+const GROUP_SEPARATOR: &str = "================================================================================";
+
+/// Separator of license texts in the notices file.
+const TEXT_SEPARATOR: &str = "--------------------------------------------------------------------------------";
+
+/// Prefixes of license file names, compared case-insensitively.
+const LICENSE_FILE_PREFIXES: [&str; 3] = ["LICENSE", "LICENCE", "COPYING"];
+
+/// Dependency having a license not allowed by the policy.
+pub struct Violation {
+  /// Name of the dependency.
+  pub name: String,
+  /// Version of the dependency.
+  pub version: String,
+  /// License expression of the dependency.
+  pub license: String,
+}
+
+/// Dependency whose licenses could not be collected.
+pub struct Unresolved {
+  /// Name of the dependency.
+  pub name: String,
+  /// Version of the dependency.
+  pub version: String,
+  /// Reason why the licenses could not be collected.
+  pub reason: String,
+}
+
+/// Result of the license audit.
+pub struct Audit {
+  /// Dependencies having licenses not allowed by the policy.
+  pub violations: Vec<Violation>,
+  /// Dependencies whose licenses could not be collected, the notices do not cover them.
+  pub unresolved: Vec<Unresolved>,
+  /// Content of the notices file.
+  pub notices: String,
+}
+
+/// Third-party dependency listed in the lock file.
+struct Dependency {
+  /// Name of the dependency.
+  name: String,
+  /// Version of the dependency.
+  version: String,
+}
+
+/// Audits licenses of all third-party dependencies of the package.
+pub fn audit(manifest: &Manifest) -> Result<Audit> {
+  let allowed = allowed_licenses(manifest)?;
+  let is_allowed = |license: &str| {
+    allowed
+      .as_ref()
+      .is_none_or(|allowed| allowed.iter().any(|id| base_id(id).eq_ignore_ascii_case(base_id(license))))
+  };
+  let lock_dir = lock_dir(manifest)?;
+  let vendor_dir = lock_dir.join(manifest.metadata_str("licenses.vendor")?.unwrap_or(DEFAULT_VENDOR_DIR));
+  let registry_dirs = registry_dirs();
+  let mut violations = vec![];
+  let mut unresolved = vec![];
+  // Texts of licenses, keyed by license, each text with the list of dependencies using it.
+  let mut groups = BTreeMap::<String, Vec<(String, Vec<String>)>>::new();
+  for dependency in dependencies(&lock_dir)? {
+    let label = format!("{} {}", dependency.name, dependency.version);
+    let mut unresolve = |reason: String| {
+      unresolved.push(Unresolved {
+        name: dependency.name.clone(),
+        version: dependency.version.clone(),
+        reason,
+      })
+    };
+    let Some(dir) = source_dir(&dependency, &vendor_dir, &registry_dirs) else {
+      unresolve("sources not found locally, run `cargo fetch` or `cargo vendor`".to_string());
+      continue;
+    };
+    let parsed = match parse_toml(dir.join(MANIFEST_FILE_NAME)) {
+      Ok(parsed) => parsed,
+      Err(reason) => {
+        unresolve(reason.to_string());
+        continue;
+      }
+    };
+    let package = parsed.get("package");
+    let field = |key: &str| package.and_then(|package| package.get(key)).and_then(|value| value.as_str());
+    let licenses = match (field("license"), field("license-file")) {
+      (Some(license), _) => {
+        let expression = match Expression::parse(license) {
+          Ok(expression) => expression,
+          Err(reason) => {
+            unresolve(format!("invalid license: {reason}"));
+            continue;
+          }
+        };
+        let licenses = match expression.satisfy(&is_allowed) {
+          Some(licenses) => licenses,
+          None => {
+            violations.push(Violation {
+              name: dependency.name.clone(),
+              version: dependency.version.clone(),
+              license: license.to_string(),
+            });
+            expression.licenses()
+          }
+        };
+        let count = expression.licenses().len();
+        licenses
+          .into_iter()
+          .map(|(license_id, exception_id)| (group_name(license_id, exception_id), license_text(&dir, count, license_id, exception_id, package)))
+          .collect::<Vec<(String, Option<String>)>>()
+      }
+      (None, Some(license_file)) => {
+        let license = format!("LicenseRef-{}", dependency.name);
+        // Licenses referenced by files can not be verified against the policy.
+        if allowed.is_some() {
+          violations.push(Violation {
+            name: dependency.name.clone(),
+            version: dependency.version.clone(),
+            license: license.clone(),
+          });
+        }
+        vec![(license, read_file(dir.join(license_file)).ok())]
+      }
+      (None, None) => {
+        unresolve("license not specified in Cargo.toml".to_string());
+        continue;
+      }
+    };
+    for (group, text) in licenses {
+      let Some(text) = text else {
+        unresolve(format!("text of license {group} not found"));
+        continue;
+      };
+      let texts = groups.entry(group).or_default();
+      let text = text.trim().to_string();
+      match texts.iter_mut().find(|(existing, _)| *existing == text) {
+        Some((_, labels)) => labels.push(label.clone()),
+        None => texts.push((text, vec![label.clone()])),
+      }
+    }
+  }
+  Ok(Audit {
+    violations,
+    unresolved,
+    notices: notices(manifest.name()?, &groups),
+  })
+}
+
+/// Returns the allowed licenses configured in package metadata, `None` when all licenses are allowed.
+fn allowed_licenses(manifest: &Manifest) -> Result<Option<Vec<String>>> {
+  match manifest.metadata("licenses.allowed") {
+    None => Ok(None),
+    Some(toml::Value::Array(licenses)) => Ok(Some(licenses.iter().filter_map(|license| license.as_str()).map(|license| license.to_string()).collect())),
+    Some(_) => Err(MaggError::manifest("metadata.magg.licenses.allowed in Cargo.toml must be an array of strings")),
+  }
+}
+
+/// Returns the directory containing the lock file, the package directory or the workspace root.
+fn lock_dir(manifest: &Manifest) -> Result<PathBuf> {
+  if manifest.dir().join(LOCK_FILE_NAME).exists() {
+    return Ok(manifest.dir().to_path_buf());
+  }
+  match Workspace::find(manifest.dir()) {
+    Ok(workspace) if workspace.dir().join(LOCK_FILE_NAME).exists() => Ok(workspace.dir().to_path_buf()),
+    _ => Err(MaggError::manifest(format!("{LOCK_FILE_NAME} not found, run `cargo generate-lockfile`"))),
+  }
+}
+
+/// Returns third-party dependencies listed in the lock file, local packages are skipped.
+fn dependencies(lock_dir: &Path) -> Result<Vec<Dependency>> {
+  let parsed = parse_toml(lock_dir.join(LOCK_FILE_NAME))?;
+  let packages = parsed.get("package").and_then(|packages| packages.as_array()).cloned().unwrap_or_default();
+  Ok(
+    packages
+      .iter()
+      .filter(|package| package.get("source").is_some())
+      .filter_map(|package| {
+        Some(Dependency {
+          name: package.get("name")?.as_str()?.to_string(),
+          version: package.get("version")?.as_str()?.to_string(),
+        })
+      })
+      .collect(),
+  )
+}
+
+/// Returns the directories of unpacked crates in the local Cargo registry.
+fn registry_dirs() -> Vec<PathBuf> {
+  let cargo_home = std::env::var_os("CARGO_HOME")
+    .map(PathBuf::from)
+    .or_else(|| std::env::home_dir().map(|home| home.join(".cargo")));
+  let Some(Ok(entries)) = cargo_home.map(|cargo_home| std::fs::read_dir(cargo_home.join("registry").join("src"))) else {
+    return vec![];
+  };
+  let mut dirs = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect::<Vec<PathBuf>>();
+  dirs.sort();
+  dirs
+}
+
+/// Returns the directory with the sources of the dependency, searching the vendor directory first.
+/// Directories named after the dependency and its version are matched by name only,
+/// so their invalid manifests are reported instead of being skipped.
+fn source_dir(dependency: &Dependency, vendor_dir: &Path, registry_dirs: &[PathBuf]) -> Option<PathBuf> {
+  let versioned = format!("{}-{}", dependency.name, dependency.version);
+  let has_manifest = |dir: &PathBuf| dir.join(MANIFEST_FILE_NAME).is_file();
+  let has_version = |dir: &PathBuf| {
+    parse_toml(dir.join(MANIFEST_FILE_NAME))
+      .ok()
+      .and_then(|parsed| parsed.get("package")?.get("version")?.as_str().map(|version| version == dependency.version))
+      .unwrap_or(false)
+  };
+  Some(vendor_dir.join(&versioned))
+    .filter(has_manifest)
+    .or_else(|| Some(vendor_dir.join(&dependency.name)).filter(has_version))
+    .or_else(|| registry_dirs.iter().map(|dir| dir.join(&versioned)).find(has_manifest))
+}
+
+/// Returns the name of the license group.
+fn group_name(license_id: &str, exception_id: Option<&str>) -> String {
+  match exception_id {
+    Some(exception_id) => format!("{license_id} WITH {exception_id}"),
+    None => license_id.to_string(),
+  }
+}
+
+/// Returns the text of the license, read from the license file of the dependency,
+/// or taken from the bundled license catalogue when the dependency has no matching license file.
+/// `count` is the number of licenses in the license expression of the dependency.
+fn license_text(dir: &Path, count: usize, license_id: &str, exception_id: Option<&str>, package: Option<&toml::Value>) -> Option<String> {
+  let file_names = license_file_names(dir);
+  // Licenses are matched by the first part of the identifier, like `LICENSE-APACHE` for `Apache-2.0`.
+  let key = base_id(license_id).split('-').next().unwrap_or_default().to_uppercase();
+  let file_name = if count == 1 && file_names.len() == 1 {
+    file_names.first()
+  } else {
+    file_names.iter().find(|file_name| file_name.to_uppercase().contains(&key))
+  };
+  if let Some(file_name) = file_name {
+    return read_file(dir.join(file_name)).ok();
+  }
+  let (_, text) = find(&LICENSES, base_id(license_id))?;
+  let authors = package
+    .and_then(|package| package.get("authors"))
+    .and_then(|authors| authors.as_array())
+    .map(|authors| {
+      authors
+        .iter()
+        .filter_map(|author| author.as_str())
+        .map(|author| author.split('<').next().unwrap_or_default().trim())
+        .collect::<Vec<&str>>()
+        .join(", ")
+    })
+    .filter(|authors| !authors.is_empty());
+  let name = package.and_then(|package| package.get("name")).and_then(|name| name.as_str()).unwrap_or_default();
+  let mut text = text.replace("[YEARS] [COPYRIGHT_OWNER]", &authors.unwrap_or_else(|| format!("the {name} authors")));
+  if let Some(exception_id) = exception_id {
+    let (_, exception) = find(&EXCEPTIONS, exception_id)?;
+    text.push('\n');
+    text.push_str(exception);
+  }
+  Some(text)
+}
+
+/// Returns sorted names of license files in the directory.
+fn license_file_names(dir: &Path) -> Vec<String> {
+  let Ok(entries) = std::fs::read_dir(dir) else {
+    return vec![];
+  };
+  let mut file_names = entries
+    .filter_map(|entry| entry.ok())
+    .filter(|entry| entry.path().is_file())
+    .map(|entry| entry.file_name().to_string_lossy().to_string())
+    .filter(|file_name| LICENSE_FILE_PREFIXES.iter().any(|prefix| file_name.to_uppercase().starts_with(prefix)) || file_name.eq_ignore_ascii_case("UNLICENSE"))
+    .collect::<Vec<String>>();
+  file_names.sort();
+  file_names
+}
+
+/// Returns the content of the notices file.
+fn notices(name: &str, groups: &BTreeMap<String, Vec<(String, Vec<String>)>>) -> String {
+  let mut output = String::new();
+  _ = writeln!(&mut output, "THIRD-PARTY NOTICES\n");
+  _ = writeln!(&mut output, "{name} uses the following third-party software, grouped by license.");
+  for (license, texts) in groups {
+    _ = writeln!(&mut output, "\n{GROUP_SEPARATOR}\n{license}\n{GROUP_SEPARATOR}");
+    for (index, (text, labels)) in texts.iter().enumerate() {
+      if index > 0 {
+        _ = writeln!(&mut output, "\n{TEXT_SEPARATOR}");
+      }
+      _ = writeln!(&mut output, "\nUsed by:\n");
+      for label in labels {
+        _ = writeln!(&mut output, "- {label}");
+      }
+      _ = writeln!(&mut output, "\n{text}");
+    }
+  }
+  output
+}
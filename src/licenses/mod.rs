@@ -1,31 +1,210 @@
 //! License generator
+//!
+//! Generates license files matching the SPDX license expression
+//! given in `package.license`, using the bundled license catalogue,
+//! and checks existing license files against the catalogue.
 
-use time::OffsetDateTime;
+mod audit;
+mod spdx;
+
+use crate::copyright::Copyright;
+use crate::errors::*;
+use crate::manifest::Manifest;
+use crate::utils::read_file;
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+pub use audit::{NOTICES_FILE_NAME, audit};
+pub use spdx::Expression;
 
 const APACHE_2: &str = include_str!("APACHE-2");
 const APACHE_NOTICE: &str = include_str!("APACHE-NOTICE");
 const MIT: &str = include_str!("MIT");
-const START_YEAR: &str = "2015";
-const COPYRIGHT_OWNER: &str = "Dariusz Depta";
 
-pub fn get_apache_2() -> String {
-  APACHE_2.to_string()
+/// Identifier of the Apache License 2.0, the only license generating the NOTICE file.
+const APACHE_2_ID: &str = "Apache-2.0";
+
+/// Bundled license texts, keyed by SPDX license identifier without `-only` and `-or-later` suffixes.
+const LICENSES: [(&str, &str); 16] = [
+  ("0BSD", include_str!("0BSD")),
+  ("AGPL-3.0", include_str!("AGPL-3.0")),
+  (APACHE_2_ID, APACHE_2),
+  ("BSD-2-Clause", include_str!("BSD-2-Clause")),
+  ("BSD-3-Clause", include_str!("BSD-3-Clause")),
+  ("BSL-1.0", include_str!("BSL-1.0")),
+  ("CC0-1.0", include_str!("CC0-1.0")),
+  ("GPL-2.0", include_str!("GPL-2.0")),
+  ("GPL-3.0", include_str!("GPL-3.0")),
+  ("ISC", include_str!("ISC")),
+  ("LGPL-2.1", include_str!("LGPL-2.1")),
+  ("LGPL-3.0", include_str!("LGPL-3.0")),
+  ("MIT", MIT),
+  ("MPL-2.0", include_str!("MPL-2.0")),
+  ("Unlicense", include_str!("Unlicense")),
+  ("Zlib", include_str!("Zlib")),
+];
+
+/// Full names of bundled licenses, keyed by SPDX license identifier without `-only` and `-or-later` suffixes.
+const NAMES: [(&str, &str); 16] = [
+  ("0BSD", "BSD Zero Clause License"),
+  ("AGPL-3.0", "GNU Affero General Public License v3.0"),
+  (APACHE_2_ID, "Apache License, Version 2.0"),
+  ("BSD-2-Clause", "BSD 2-Clause License"),
+  ("BSD-3-Clause", "BSD 3-Clause License"),
+  ("BSL-1.0", "Boost Software License 1.0"),
+  ("CC0-1.0", "Creative Commons Zero v1.0 Universal"),
+  ("GPL-2.0", "GNU General Public License v2.0"),
+  ("GPL-3.0", "GNU General Public License v3.0"),
+  ("ISC", "ISC License"),
+  ("LGPL-2.1", "GNU Lesser General Public License v2.1"),
+  ("LGPL-3.0", "GNU Lesser General Public License v3.0"),
+  ("MIT", "MIT license"),
+  ("MPL-2.0", "Mozilla Public License 2.0"),
+  ("Unlicense", "The Unlicense"),
+  ("Zlib", "zlib License"),
+];
+
+/// Name of the file accompanying the Apache License 2.0.
+const NOTICE_FILE_NAME: &str = "NOTICE";
+
+/// Bundled license exception texts, keyed by SPDX exception identifier.
+const EXCEPTIONS: [(&str, &str); 1] = [("LLVM-exception", include_str!("LLVM-exception"))];
+
+/// Prefixes of names of files considered as license files, compared case-insensitively.
+const LICENSE_FILE_PREFIXES: [&str; 3] = ["LICENSE", "COPYING", "NOTICE"];
+
+/// Pattern for matching copyright lines, like `Copyright (c) 2015-2026 Dariusz Depta`.
+const COPYRIGHT_LINE_PATTERN: &str = r#"(?i)^\s*copyright\s+(\(c\)|©|\d)"#;
+
+/// Regular expression for matching copyright lines.
+static RE_COPYRIGHT_LINE: LazyLock<Regex> = LazyLock::new(|| Regex::new(COPYRIGHT_LINE_PATTERN).unwrap());
+
+/// Status of the license file on disk.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Status {
+  /// The license file is required by the license of the package, but it does not exist.
+  Missing,
+  /// The license file exists, but it is not required by the license of the package.
+  Extra,
+  /// The content of the license file differs from the canonical license text.
+  Modified,
+}
+
+/// Returns the names and contents of license files matching the license of the package.
+///
+/// When the license expression contains a single license, its text is written to `LICENSE` file.
+/// Otherwise, each license is written to `LICENSE-<identifier>` file, except the Apache License 2.0,
+/// which is always written to `LICENSE` file. The Apache License 2.0 is accompanied by `NOTICE` file.
+pub fn get_license_files(manifest: &Manifest) -> Result<Vec<(String, String)>> {
+  let license = manifest
+    .package_str("license")?
+    .ok_or_else(|| MaggError::manifest("package.license not found in Cargo.toml"))?;
+  let expression = Expression::parse(license)?;
+  let copyright = Copyright::resolve(manifest)?;
+  let licenses = expression.licenses();
+  let mut files = vec![];
+  for (license_id, exception_id) in &licenses {
+    let (id, text) = find(&LICENSES, base_id(license_id)).ok_or_else(|| MaggError::validation(format!("unknown license identifier: {license_id}")))?;
+    let mut content = substitute(text, &copyright);
+    if let Some(exception_id) = exception_id {
+      let (_, text) = find(&EXCEPTIONS, exception_id).ok_or_else(|| MaggError::validation(format!("unknown license exception identifier: {exception_id}")))?;
+      content.push('\n');
+      content.push_str(text);
+    }
+    files.push((license_file_name(id, licenses.len()), content));
+    if id == APACHE_2_ID {
+      files.push((NOTICE_FILE_NAME.to_string(), substitute(APACHE_NOTICE, &copyright)));
+    }
+  }
+  Ok(files)
+}
+
+/// Returns licenses with optional exceptions used in the expression, each with the names of its license files,
+/// the same as the names of files returned by [get_license_files].
+pub fn license_file_names(expression: &Expression) -> Vec<(&str, Option<&str>, Vec<String>)> {
+  let licenses = expression.licenses();
+  let count = licenses.len();
+  licenses
+    .into_iter()
+    .map(|(license_id, exception_id)| {
+      let id = find(&LICENSES, base_id(license_id)).map(|(id, _)| id).unwrap_or(license_id);
+      let mut file_names = vec![license_file_name(id, count)];
+      if id == APACHE_2_ID {
+        file_names.push(NOTICE_FILE_NAME.to_string());
+      }
+      (license_id, exception_id, file_names)
+    })
+    .collect()
+}
+
+/// Returns the full name of the license, like `Mozilla Public License 2.0` for `MPL-2.0`, when it is bundled.
+pub fn license_name(id: &str) -> Option<&'static str> {
+  find(&NAMES, base_id(id)).map(|(_, name)| name)
+}
+
+/// Compares license files in the specified directory with license files matching the license of the package.
+/// Texts are compared with normalized whitespace, copyright lines are ignored.
+/// Returns the names and statuses of license files that are missing, extra or modified.
+pub fn check_license_files(manifest: &Manifest, dir: &Path) -> Result<Vec<(String, Status)>> {
+  let expected = get_license_files(manifest)?;
+  let mut statuses = vec![];
+  for (file_name, content) in &expected {
+    let path = dir.join(file_name);
+    if !path.exists() {
+      statuses.push((file_name.clone(), Status::Missing));
+    } else if normalize(&read_file(&path)?) != normalize(content) {
+      statuses.push((file_name.clone(), Status::Modified));
+    }
+  }
+  let root = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+  if !root.exists() {
+    return Ok(statuses);
+  }
+  let entries = std::fs::read_dir(root).map_err(|e| MaggError::io(format!("failed to list files in {}", root.display()), e))?;
+  let mut extra = entries
+    .filter_map(|entry| entry.ok())
+    .filter(|entry| entry.path().is_file())
+    .map(|entry| entry.file_name().to_string_lossy().to_string())
+    .filter(|file_name| LICENSE_FILE_PREFIXES.iter().any(|prefix| file_name.to_uppercase().starts_with(prefix)))
+    .filter(|file_name| !expected.iter().any(|(expected, _)| expected == file_name))
+    .collect::<Vec<String>>();
+  extra.sort();
+  statuses.extend(extra.into_iter().map(|file_name| (file_name, Status::Extra)));
+  Ok(statuses)
+}
+
+/// Returns the license text with collapsed whitespace and without copyright lines.
+fn normalize(text: &str) -> String {
+  text
+    .lines()
+    .filter(|line| !RE_COPYRIGHT_LINE.is_match(line))
+    .flat_map(|line| line.split_whitespace())
+    .collect::<Vec<&str>>()
+    .join(" ")
+}
+
+/// Replaces placeholders in the license template.
+fn substitute(template: &str, copyright: &Copyright) -> String {
+  template.replace("[YEARS]", &copyright.years()).replace("[COPYRIGHT_OWNER]", &copyright.holders)
 }
 
-pub fn get_apache_notice() -> String {
-  APACHE_NOTICE
-    .replace("[START_YEAR]", START_YEAR)
-    .replace("[END_YEAR]", &get_year())
-    .replace("[COPYRIGHT_OWNER]", COPYRIGHT_OWNER)
+/// Returns the name of the file containing the license text, `LICENSE` when the expression contains a single license,
+/// otherwise `LICENSE-<identifier>`, except the Apache License 2.0, which is always written to `LICENSE` file.
+fn license_file_name(id: &str, license_count: usize) -> String {
+  if license_count == 1 || id == APACHE_2_ID {
+    "LICENSE".to_string()
+  } else {
+    format!("LICENSE-{id}")
+  }
 }
 
-pub fn get_mit() -> String {
-  MIT
-    .replace("[START_YEAR]", START_YEAR)
-    .replace("[END_YEAR]", &get_year())
-    .replace("[COPYRIGHT_OWNER]", COPYRIGHT_OWNER)
+/// Returns the license identifier without `-only`, `-or-later` and `+` suffixes.
+fn base_id(id: &str) -> &str {
+  ["-only", "-or-later", "+"].iter().fold(id, |id, suffix| id.strip_suffix(suffix).unwrap_or(id))
 }
 
-fn get_year() -> String {
-  format!("{}", OffsetDateTime::now_utc().year())
+/// Finds the text by identifier, identifiers are case-insensitive.
+fn find<'a>(catalogue: &[(&'a str, &'a str)], id: &str) -> Option<(&'a str, &'a str)> {
+  catalogue.iter().find(|(key, _)| key.eq_ignore_ascii_case(id)).copied()
 }
@@ -0,0 +1,165 @@
+//! # SPDX license expression parser
+//!
+//! Parses license expressions like `MIT OR Apache-2.0`, `Apache-2.0 WITH LLVM-exception`
+//! or `(MIT OR Apache-2.0) AND BSD-3-Clause`. The legacy `/` separator, like in `MIT/Apache-2.0`,
+//! is accepted as an alternative of `OR`.
+
+use crate::errors::*;
+
+/// License expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+  /// License identifier with an optional exception identifier.
+  License(String, Option<String>),
+  /// Both expressions apply.
+  And(Box<Expression>, Box<Expression>),
+  /// Any of the expressions applies.
+  Or(Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+  /// Parses the license expression.
+  pub fn parse(input: &str) -> Result<Self> {
+    let tokens = tokenize(input);
+    let mut parser = Parser {
+      tokens: &tokens,
+      position: 0,
+      input,
+    };
+    let expression = parser.or_expression()?;
+    if parser.position < tokens.len() {
+      return Err(parser.unexpected());
+    }
+    Ok(expression)
+  }
+
+  /// Returns all licenses with optional exceptions used in the expression, in the order of appearance.
+  pub fn licenses(&self) -> Vec<(&str, Option<&str>)> {
+    match self {
+      Expression::License(license, exception) => vec![(license.as_str(), exception.as_deref())],
+      Expression::And(left, right) | Expression::Or(left, right) => {
+        let mut licenses = left.licenses();
+        for license in right.licenses() {
+          if !licenses.contains(&license) {
+            licenses.push(license);
+          }
+        }
+        licenses
+      }
+    }
+  }
+
+  /// Returns licenses with optional exceptions under which the expression is satisfied,
+  /// choosing the first allowed alternative of `OR` expressions.
+  /// Returns `None` when the expression can not be satisfied with allowed licenses.
+  pub fn satisfy(&self, allowed: &impl Fn(&str) -> bool) -> Option<Vec<(&str, Option<&str>)>> {
+    match self {
+      Expression::License(license, exception) => allowed(license).then(|| vec![(license.as_str(), exception.as_deref())]),
+      Expression::And(left, right) => {
+        let mut licenses = left.satisfy(allowed)?;
+        for license in right.satisfy(allowed)? {
+          if !licenses.contains(&license) {
+            licenses.push(license);
+          }
+        }
+        Some(licenses)
+      }
+      Expression::Or(left, right) => left.satisfy(allowed).or_else(|| right.satisfy(allowed)),
+    }
+  }
+}
+
+/// Splits the license expression into tokens.
+fn tokenize(input: &str) -> Vec<String> {
+  let mut tokens = vec![];
+  let mut token = String::new();
+  for ch in input.chars() {
+    if ch.is_whitespace() || matches!(ch, '(' | ')' | '/') {
+      if !token.is_empty() {
+        tokens.push(std::mem::take(&mut token));
+      }
+      if !ch.is_whitespace() {
+        tokens.push(ch.to_string());
+      }
+    } else {
+      token.push(ch);
+    }
+  }
+  if !token.is_empty() {
+    tokens.push(token);
+  }
+  tokens
+}
+
+/// Recursive descent parser of license expressions.
+struct Parser<'a> {
+  /// Tokens of the parsed expression.
+  tokens: &'a [String],
+  /// Position of the current token.
+  position: usize,
+  /// Parsed expression, used in error messages.
+  input: &'a str,
+}
+
+impl Parser<'_> {
+  /// Parses `and-expression ( OR and-expression )*`.
+  fn or_expression(&mut self) -> Result<Expression> {
+    let mut expression = self.and_expression()?;
+    while self.accept("OR") || self.accept("/") {
+      expression = Expression::Or(Box::new(expression), Box::new(self.and_expression()?));
+    }
+    Ok(expression)
+  }
+
+  /// Parses `with-expression ( AND with-expression )*`.
+  fn and_expression(&mut self) -> Result<Expression> {
+    let mut expression = self.with_expression()?;
+    while self.accept("AND") {
+      expression = Expression::And(Box::new(expression), Box::new(self.with_expression()?));
+    }
+    Ok(expression)
+  }
+
+  /// Parses `license ( WITH exception )?` or a parenthesized expression.
+  fn with_expression(&mut self) -> Result<Expression> {
+    if self.accept("(") {
+      let expression = self.or_expression()?;
+      if !self.accept(")") {
+        return Err(self.unexpected());
+      }
+      return Ok(expression);
+    }
+    let license = self.identifier()?;
+    let exception = if self.accept("WITH") { Some(self.identifier()?) } else { None };
+    Ok(Expression::License(license, exception))
+  }
+
+  /// Parses the license or exception identifier.
+  fn identifier(&mut self) -> Result<String> {
+    match self.tokens.get(self.position) {
+      Some(token) if !matches!(token.as_str(), "AND" | "OR" | "WITH" | "(" | ")" | "/") => {
+        self.position += 1;
+        Ok(token.clone())
+      }
+      _ => Err(self.unexpected()),
+    }
+  }
+
+  /// Consumes the current token when it is equal to the expected one.
+  fn accept(&mut self, expected: &str) -> bool {
+    if self.tokens.get(self.position).is_some_and(|token| token == expected) {
+      self.position += 1;
+      true
+    } else {
+      false
+    }
+  }
+
+  /// Returns an error reporting the unexpected token.
+  fn unexpected(&self) -> MaggError {
+    match self.tokens.get(self.position) {
+      Some(token) => MaggError::parse(format!("unexpected '{}' in license expression: {}", token, self.input)),
+      None => MaggError::parse(format!("unexpected end of license expression: {}", self.input)),
+    }
+  }
+}
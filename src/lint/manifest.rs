@@ -0,0 +1,448 @@
+//! # Manifest linter
+//!
+//! Checks the package manifest against crates.io rules and the team policy:
+//! `description`, `license`, `repository`, `readme`, `keywords`, `categories` and `rust-version`
+//! must be set correctly, development files must be excluded from the package,
+//! and `exclude` (or `include`) must not leave out files included in sources
+//! with `include_str!` or `include_bytes!` macros.
+
+use super::Diagnostic;
+use crate::errors::*;
+use crate::licenses::Expression;
+use crate::manifest::{MANIFEST_FILE_NAME, Manifest};
+use crate::repository::Repository;
+use crate::utils::read_file;
+use crate::writer::RECORD_FILE_NAME;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use regex::Regex;
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Maximum number of keywords and categories accepted by crates.io.
+const MAX_ENTRIES: usize = 5;
+
+/// Valid category slugs accepted by crates.io.
+const CATEGORIES: &str = include_str!("CATEGORIES");
+
+/// Development files that should not be published, according to the team policy.
+const DEVELOPMENT_FILES: [&str; 7] = [".github", ".gitignore", RECORD_FILE_NAME, "CODE_OF_CONDUCT.md", "Taskfile.yml", "manual", "rustfmt.toml"];
+
+/// Pattern for matching valid keywords.
+const KEYWORD_PATTERN: &str = r#"^[a-zA-Z0-9][a-zA-Z0-9_+-]{0,19}$"#;
+
+/// Regular expression for matching valid keywords.
+static RE_KEYWORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(KEYWORD_PATTERN).unwrap());
+
+/// Pattern for matching valid minimal supported Rust versions, like `1.85` or `1.85.1`.
+const RUST_VERSION_PATTERN: &str = r#"^\d+\.\d+(\.\d+)?$"#;
+
+/// Regular expression for matching valid minimal supported Rust versions.
+static RE_RUST_VERSION: LazyLock<Regex> = LazyLock::new(|| Regex::new(RUST_VERSION_PATTERN).unwrap());
+
+/// Pattern for matching files included in sources, like `include_str!("APACHE-2")`.
+const INCLUDE_PATTERN: &str = r#"include_(?:str|bytes)!\s*\(\s*"([^"]+)"\s*\)"#;
+
+/// Regular expression for matching files included in sources.
+static RE_INCLUDE: LazyLock<Regex> = LazyLock::new(|| Regex::new(INCLUDE_PATTERN).unwrap());
+
+/// Severity of the reported problem.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+  /// The package does not follow the team policy.
+  Warning,
+  /// The package violates crates.io rules or will not build when published.
+  Error,
+}
+
+/// Problem reported by the manifest linter, with the suggested fix.
+pub struct Finding {
+  /// Severity of the problem.
+  pub severity: Severity,
+  /// Location and description of the problem.
+  pub diagnostic: Diagnostic,
+  /// Suggested fix.
+  pub suggestion: String,
+}
+
+impl Display for Finding {
+  /// Implementation of [Display] trait for [Finding].
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let severity = match self.severity {
+      Severity::Warning => "warning",
+      Severity::Error => "error",
+    };
+    write!(f, "{severity}: {}\n  help: {}", self.diagnostic, self.suggestion)
+  }
+}
+
+/// Collected findings, located in the package manifest.
+struct Findings {
+  /// Path of the package manifest.
+  file_name: PathBuf,
+  /// Content of the package manifest.
+  content: String,
+  /// Reported findings.
+  findings: Vec<Finding>,
+}
+
+impl Findings {
+  /// Reports the problem with the specified package field.
+  fn add(&mut self, severity: Severity, key: &str, message: impl ToString, suggestion: impl ToString) {
+    let diagnostic = Diagnostic {
+      file_name: self.file_name.clone(),
+      line: line_of(&self.content, key),
+      message: message.to_string(),
+    };
+    self.findings.push(Finding {
+      severity,
+      diagnostic,
+      suggestion: suggestion.to_string(),
+    });
+  }
+}
+
+/// Checks the package manifest.
+pub fn lint(manifest: &Manifest) -> Result<Vec<Finding>> {
+  let file_name = manifest.dir().join(MANIFEST_FILE_NAME);
+  let content = read_file(&file_name)?;
+  let mut findings = Findings {
+    file_name,
+    content,
+    findings: vec![],
+  };
+  check_description(manifest, &mut findings)?;
+  check_license(manifest, &mut findings)?;
+  check_repository(manifest, &mut findings)?;
+  check_readme(manifest, &mut findings);
+  check_list(manifest, &mut findings, "keywords", |keyword| {
+    (!RE_KEYWORD.is_match(keyword)).then(|| {
+      (
+        format!("invalid keyword '{keyword}'"),
+        "keywords must start with a letter or digit, contain only letters, digits, '_', '-' or '+', and have at most 20 characters".to_string(),
+      )
+    })
+  })?;
+  check_list(manifest, &mut findings, "categories", |category| {
+    (!CATEGORIES.lines().any(|slug| slug == category)).then(|| {
+      let suggestion = match similar_category(category) {
+        Some(slug) => format!("did you mean '{slug}'? see https://crates.io/category_slugs"),
+        None => "use one of the slugs listed at https://crates.io/category_slugs".to_string(),
+      };
+      (format!("invalid category '{category}'"), suggestion)
+    })
+  })?;
+  check_rust_version(manifest, &mut findings)?;
+  check_packaged_files(manifest, &mut findings)?;
+  Ok(findings.findings)
+}
+
+/// Checks if the description is set, it is required by crates.io.
+fn check_description(manifest: &Manifest, findings: &mut Findings) -> Result<()> {
+  if manifest.package_str("description")?.is_none_or(|description| description.trim().is_empty()) {
+    findings.add(
+      Severity::Error,
+      "description",
+      "package.description is not set, it is required by crates.io",
+      "add a short description, like: description = \"Sophisticated tooling for Rust developers\"",
+    );
+  }
+  Ok(())
+}
+
+/// Checks if the license is a valid SPDX expression, the license or the license file is required by crates.io.
+fn check_license(manifest: &Manifest, findings: &mut Findings) -> Result<()> {
+  match manifest.package_str("license")? {
+    Some(license) => {
+      if let Err(reason) = Expression::parse(license) {
+        findings.add(
+          Severity::Error,
+          "license",
+          format!("package.license is not a valid SPDX expression: {reason}"),
+          "use SPDX license identifiers, like: license = \"MIT OR Apache-2.0\"",
+        );
+      }
+    }
+    None if manifest.package("license-file").is_none() => findings.add(
+      Severity::Error,
+      "license",
+      "package.license is not set, it is required by crates.io",
+      "add an SPDX license expression, like: license = \"MIT OR Apache-2.0\"",
+    ),
+    None => findings.add(
+      Severity::Warning,
+      "license-file",
+      "package.license-file is used instead of package.license",
+      "prefer an SPDX license expression, like: license = \"MIT OR Apache-2.0\"",
+    ),
+  }
+  Ok(())
+}
+
+/// Checks if the repository URL is set and valid, it is required by magg commands.
+fn check_repository(manifest: &Manifest, findings: &mut Findings) -> Result<()> {
+  match manifest.package_str("repository")? {
+    Some(repository) => {
+      if let Err(reason) = Repository::parse(repository) {
+        findings.add(
+          Severity::Error,
+          "repository",
+          reason,
+          "use the URL of the repository, like: repository = \"https://github.com/owner/name\"",
+        );
+      }
+    }
+    None => findings.add(
+      Severity::Error,
+      "repository",
+      "package.repository is not set",
+      "add the URL of the repository, like: repository = \"https://github.com/owner/name\"",
+    ),
+  }
+  Ok(())
+}
+
+/// Checks if the README file exists, crates.io displays it on the crate page.
+fn check_readme(manifest: &Manifest, findings: &mut Findings) {
+  match manifest.package("readme") {
+    Some(toml::Value::String(readme)) => {
+      if !manifest.dir().join(readme).exists() {
+        findings.add(
+          Severity::Error,
+          "readme",
+          format!("README file '{readme}' does not exist"),
+          "create the file with `magg readme` or correct package.readme",
+        );
+      }
+    }
+    Some(toml::Value::Boolean(false)) => findings.add(
+      Severity::Warning,
+      "readme",
+      "package.readme is disabled, crates.io will display no README",
+      "remove readme = false and generate README.md with `magg readme`",
+    ),
+    _ => {
+      // Cargo publishes README.md automatically, when it is not specified.
+      if !manifest.dir().join("README.md").exists() {
+        findings.add(
+          Severity::Warning,
+          "readme",
+          "README file not found, crates.io will display no README",
+          "generate README.md with `magg readme`",
+        );
+      }
+    }
+  }
+}
+
+/// Checks the number and validity of entries in the list of keywords or categories.
+fn check_list(manifest: &Manifest, findings: &mut Findings, key: &str, invalid: impl Fn(&str) -> Option<(String, String)>) -> Result<()> {
+  let Some(value) = manifest.package(key) else {
+    findings.add(
+      Severity::Warning,
+      key,
+      format!("package.{key} is not set, the crate will be harder to find on crates.io"),
+      format!("add up to {MAX_ENTRIES} {key}"),
+    );
+    return Ok(());
+  };
+  let entries = value
+    .as_array()
+    .and_then(|entries| entries.iter().map(|entry| entry.as_str()).collect::<Option<Vec<&str>>>())
+    .ok_or_else(|| MaggError::manifest(format!("package.{key} in Cargo.toml must be an array of strings")))?;
+  if entries.is_empty() {
+    findings.add(
+      Severity::Warning,
+      key,
+      format!("package.{key} is empty, the crate will be harder to find on crates.io"),
+      format!("add up to {MAX_ENTRIES} {key}"),
+    );
+  }
+  if entries.len() > MAX_ENTRIES {
+    findings.add(
+      Severity::Error,
+      key,
+      format!("package.{key} has {} entries, crates.io accepts at most {MAX_ENTRIES}", entries.len()),
+      format!("remove {} of the {key}", entries.len() - MAX_ENTRIES),
+    );
+  }
+  for entry in entries {
+    if let Some((message, suggestion)) = invalid(entry) {
+      findings.add(Severity::Error, key, message, suggestion);
+    }
+  }
+  Ok(())
+}
+
+/// Checks if the minimal supported Rust version is set and valid.
+fn check_rust_version(manifest: &Manifest, findings: &mut Findings) -> Result<()> {
+  match manifest.package_str("rust-version")? {
+    Some(rust_version) => {
+      if !RE_RUST_VERSION.is_match(rust_version) {
+        findings.add(
+          Severity::Error,
+          "rust-version",
+          format!("package.rust-version '{rust_version}' is not a valid Rust version"),
+          "use the version without pre-release suffix, like: rust-version = \"1.85\"",
+        );
+      }
+    }
+    None => findings.add(
+      Severity::Warning,
+      "rust-version",
+      "package.rust-version is not set",
+      "add the minimal supported Rust version, like: rust-version = \"1.85\"",
+    ),
+  }
+  Ok(())
+}
+
+/// Checks if development files are left out and files included in sources are published.
+fn check_packaged_files(manifest: &Manifest, findings: &mut Findings) -> Result<()> {
+  let include = patterns(manifest, "include")?;
+  let exclude = patterns(manifest, "exclude")?;
+  // Cargo ignores `exclude` patterns when `include` patterns are specified.
+  let (key, matcher) = if include.is_empty() { ("exclude", &exclude) } else { ("include", &include) };
+  let matcher = matcher_for(manifest, matcher)?;
+  let is_packaged = |path: &Path| {
+    let matched = matcher.matched_path_or_any_parents(path, manifest.dir().join(path).is_dir()).is_ignore();
+    if key == "include" { matched } else { !matched }
+  };
+  for file_name in DEVELOPMENT_FILES {
+    if manifest.dir().join(file_name).exists() && is_packaged(Path::new(file_name)) {
+      findings.add(
+        Severity::Warning,
+        key,
+        format!("development file '{file_name}' will be published"),
+        if key == "include" {
+          format!("remove patterns matching '{file_name}' from package.include")
+        } else {
+          format!("add '{file_name}' to package.exclude")
+        },
+      );
+    }
+  }
+  for (source, line, target) in included_files(manifest.dir())? {
+    if is_packaged(&source) && !is_packaged(&target) {
+      let suggestion = match matcher.matched_path_or_any_parents(&target, false).inner() {
+        Some(glob) if key == "exclude" => format!("remove '{}' from package.exclude", glob.original()),
+        _ => format!("add '{}' to package.{key}", target.display()),
+      };
+      findings.add(
+        Severity::Error,
+        key,
+        format!("file '{}' included in {}:{} will not be published", target.display(), source.display(), line),
+        suggestion,
+      );
+    }
+  }
+  Ok(())
+}
+
+/// Returns the patterns from the specified list of the package.
+fn patterns(manifest: &Manifest, key: &str) -> Result<Vec<String>> {
+  let Some(value) = manifest.package(key) else {
+    return Ok(vec![]);
+  };
+  value
+    .as_array()
+    .and_then(|patterns| patterns.iter().map(|pattern| pattern.as_str().map(|pattern| pattern.to_string())).collect())
+    .ok_or_else(|| MaggError::manifest(format!("package.{key} in Cargo.toml must be an array of strings")))
+}
+
+/// Returns the matcher of gitignore-style patterns, like used by Cargo.
+fn matcher_for(manifest: &Manifest, patterns: &[String]) -> Result<Gitignore> {
+  let mut builder = GitignoreBuilder::new("");
+  for pattern in patterns {
+    builder
+      .add_line(None, pattern)
+      .map_err(|e| MaggError::manifest(format!("invalid pattern '{pattern}' in {}", manifest.dir().join(MANIFEST_FILE_NAME).display())).with_source(e))?;
+  }
+  builder
+    .build()
+    .map_err(|e| MaggError::manifest(format!("invalid patterns in {}", manifest.dir().join(MANIFEST_FILE_NAME).display())).with_source(e))
+}
+
+/// Returns source files, line numbers and files included by `include_str!` or `include_bytes!` macros.
+/// All paths are relative to the package directory.
+fn included_files(dir: &Path) -> Result<Vec<(PathBuf, usize, PathBuf)>> {
+  let root = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+  let mut included = vec![];
+  let walker = ignore::WalkBuilder::new(root)
+    .require_git(false)
+    .filter_entry(|entry| !matches!(entry.file_name().to_str(), Some("target")))
+    .sort_by_file_path(|a, b| a.cmp(b))
+    .build();
+  for entry in walker {
+    let entry = entry.map_err(|e| MaggError::io(format!("failed to list files in {}", root.display()), io::Error::other(e)))?;
+    let path = entry.path();
+    if !entry.file_type().is_some_and(|file_type| file_type.is_file()) || path.extension().is_none_or(|extension| extension != "rs") {
+      continue;
+    }
+    let source = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+    for (index, line) in read_file(path)?.lines().enumerate() {
+      for captures in RE_INCLUDE.captures_iter(line) {
+        let target = normalize(&source.parent().unwrap_or(Path::new("")).join(&captures[1]));
+        included.push((source.clone(), index + 1, target));
+      }
+    }
+  }
+  Ok(included)
+}
+
+/// Returns the path with `.` and `..` components resolved lexically.
+fn normalize(path: &Path) -> PathBuf {
+  let mut normalized = PathBuf::new();
+  for component in path.components() {
+    match component {
+      std::path::Component::CurDir => {}
+      std::path::Component::ParentDir => _ = normalized.pop(),
+      component => normalized.push(component),
+    }
+  }
+  normalized
+}
+
+/// Returns the valid category slug similar to the invalid one, if any.
+fn similar_category(category: &str) -> Option<&'static str> {
+  let category = category.to_lowercase().replace([' ', '_'], "-");
+  CATEGORIES
+    .lines()
+    .map(|slug| (distance(&category, slug), slug))
+    .filter(|(distance, slug)| *distance <= category.len().div_ceil(4) || slug.ends_with(&format!("::{category}")))
+    .min_by_key(|(distance, _)| *distance)
+    .map(|(_, slug)| slug)
+}
+
+/// Returns the Levenshtein distance between two strings.
+fn distance(a: &str, b: &str) -> usize {
+  let b = b.chars().collect::<Vec<char>>();
+  let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+  for (i, a) in a.chars().enumerate() {
+    let mut current = vec![i + 1];
+    for (j, b) in b.iter().enumerate() {
+      current.push((previous[j] + usize::from(a != *b)).min(previous[j + 1] + 1).min(current[j] + 1));
+    }
+    previous = current;
+  }
+  previous[b.len()]
+}
+
+/// Returns the line number of the package field, or the line of the `[package]` header when the field is not set.
+fn line_of(content: &str, key: &str) -> usize {
+  let mut header = 1;
+  let mut in_package = false;
+  for (index, line) in content.lines().enumerate() {
+    let line = line.trim();
+    if line.starts_with('[') {
+      in_package = line == "[package]";
+      if in_package {
+        header = index + 1;
+      }
+    } else if in_package && line.strip_prefix(key).is_some_and(|rest| rest.trim_start().starts_with(['=', '.'])) {
+      return index + 1;
+    }
+  }
+  header
+}
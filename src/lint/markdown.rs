@@ -0,0 +1,173 @@
+//! # Markdown linter
+//!
+//! Reports reference-style links that are used but never defined, reference
+//! definitions that are defined but never used or defined more than once,
+//! and relative links pointing to files that do not exist.
+//! Fenced code blocks and inline code spans are not checked.
+
+use super::Diagnostic;
+use crate::errors::*;
+use crate::utils::read_file;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Pattern for matching reference definitions, like `[label]: https://example.com`.
+const DEFINITION_PATTERN: &str = r#"^ {0,3}\[([^\[\]]+)\]:\s*(\S+)"#;
+
+/// Regular expression for matching reference definitions.
+static RE_DEFINITION: LazyLock<Regex> = LazyLock::new(|| Regex::new(DEFINITION_PATTERN).unwrap());
+
+/// Pattern for matching full and collapsed references, like `[text][label]` or `[label][]`.
+const REFERENCE_PATTERN: &str = r#"\]\[([^\[\]]*)\]"#;
+
+/// Regular expression for matching full and collapsed references.
+static RE_REFERENCE: LazyLock<Regex> = LazyLock::new(|| Regex::new(REFERENCE_PATTERN).unwrap());
+
+/// Pattern for matching bracketed text, possibly being a shortcut reference, like `[label]`.
+const SHORTCUT_PATTERN: &str = r#"\[([^\[\]]+)\]"#;
+
+/// Regular expression for matching bracketed text.
+static RE_SHORTCUT: LazyLock<Regex> = LazyLock::new(|| Regex::new(SHORTCUT_PATTERN).unwrap());
+
+/// Pattern for matching destinations of inline links and images, like `[text](path)`.
+const INLINE_LINK_PATTERN: &str = r#"\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#;
+
+/// Regular expression for matching destinations of inline links and images.
+static RE_INLINE_LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(INLINE_LINK_PATTERN).unwrap());
+
+/// Reference definition.
+struct Definition {
+  /// Line number of the definition.
+  line: usize,
+  /// Flag indicating if the definition is used.
+  used: bool,
+}
+
+/// Checks the Markdown file with the specified name.
+pub fn lint_file(file_name: impl AsRef<Path>) -> Result<Vec<Diagnostic>> {
+  let file_name = file_name.as_ref();
+  let base_dir = file_name.parent().unwrap_or(Path::new(""));
+  Ok(lint(&read_file(file_name)?, file_name, base_dir))
+}
+
+/// Checks the Markdown content, relative links are resolved against the base directory.
+pub fn lint(content: &str, file_name: impl AsRef<Path>, base_dir: impl AsRef<Path>) -> Vec<Diagnostic> {
+  let file_name = file_name.as_ref();
+  let base_dir = base_dir.as_ref();
+  let lines = text_lines(content);
+  let mut diagnostics = vec![];
+  let diagnostic = |line: usize, message: String| Diagnostic {
+    file_name: file_name.to_path_buf(),
+    line,
+    message,
+  };
+  // Collect all reference definitions.
+  let mut definitions = BTreeMap::<String, Definition>::new();
+  for (line, text) in &lines {
+    if let Some(captures) = RE_DEFINITION.captures(text) {
+      let label = normalize_label(&captures[1]);
+      if let Some(definition) = definitions.get(&label) {
+        diagnostics.push(diagnostic(*line, format!("reference '{}' is already defined in line {}", &captures[1], definition.line)));
+      } else {
+        definitions.insert(label, Definition { line: *line, used: false });
+      }
+      check_destination(&captures[2], *line, base_dir, &mut diagnostics, diagnostic);
+    }
+  }
+  // Check all references and inline links.
+  for (line, text) in &lines {
+    if RE_DEFINITION.is_match(text) {
+      continue;
+    }
+    for captures in RE_REFERENCE.captures_iter(text) {
+      let start = captures.get(0).unwrap().start();
+      let label = if captures[1].is_empty() {
+        // Collapsed reference, the label is the preceding bracketed text.
+        text[..start].rfind('[').map(|open| &text[open + 1..start]).unwrap_or_default()
+      } else {
+        &captures[1]
+      };
+      match definitions.get_mut(&normalize_label(label)) {
+        Some(definition) => definition.used = true,
+        None => diagnostics.push(diagnostic(*line, format!("reference '{label}' is used but not defined"))),
+      }
+    }
+    for captures in RE_SHORTCUT.captures_iter(text) {
+      let matched = captures.get(0).unwrap();
+      let preceded_by_bracket = text[..matched.start()].ends_with(']');
+      let followed_by_bracket = text[matched.end()..].starts_with(['[', '(']);
+      if !preceded_by_bracket
+        && !followed_by_bracket
+        && let Some(definition) = definitions.get_mut(&normalize_label(&captures[1]))
+      {
+        definition.used = true;
+      }
+    }
+    for captures in RE_INLINE_LINK.captures_iter(text) {
+      check_destination(&captures[1], *line, base_dir, &mut diagnostics, diagnostic);
+    }
+  }
+  // Report unused definitions.
+  for (label, definition) in &definitions {
+    if !definition.used {
+      diagnostics.push(diagnostic(definition.line, format!("reference '{label}' is defined but not used")));
+    }
+  }
+  diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+  diagnostics
+}
+
+/// Reports relative link destinations pointing to files that do not exist.
+fn check_destination(destination: &str, line: usize, base_dir: &Path, diagnostics: &mut Vec<Diagnostic>, diagnostic: impl Fn(usize, String) -> Diagnostic) {
+  if destination.starts_with('#') || destination.starts_with("mailto:") || destination.contains("://") {
+    return;
+  }
+  let path = destination.split(['#', '?']).next().unwrap_or_default();
+  if !path.is_empty() && !base_dir.join(path).exists() {
+    diagnostics.push(diagnostic(line, format!("linked file '{path}' does not exist")));
+  }
+}
+
+/// Returns numbered lines of text, skipping fenced code blocks and masking inline code spans.
+fn text_lines(content: &str) -> Vec<(usize, String)> {
+  let mut lines = vec![];
+  let mut fence: Option<String> = None;
+  for (index, line) in content.lines().enumerate() {
+    let trimmed = line.trim_start();
+    let marker = ['`', '~'].into_iter().find_map(|ch| {
+      let count = trimmed.chars().take_while(|c| *c == ch).count();
+      (count >= 3).then(|| ch.to_string().repeat(count))
+    });
+    match (&fence, marker) {
+      (None, Some(marker)) => fence = Some(marker),
+      (Some(open), Some(marker)) if marker.starts_with(open.as_str()) && trimmed[marker.len()..].trim().is_empty() => fence = None,
+      (None, None) => lines.push((index + 1, mask_code_spans(line))),
+      _ => {}
+    }
+  }
+  lines
+}
+
+/// Replaces the content of inline code spans with spaces.
+fn mask_code_spans(line: &str) -> String {
+  let mut masked = String::with_capacity(line.len());
+  let mut inside = false;
+  for ch in line.chars() {
+    if ch == '`' {
+      inside = !inside;
+      masked.push(ch);
+    } else if inside {
+      masked.push(' ');
+    } else {
+      masked.push(ch);
+    }
+  }
+  masked
+}
+
+/// Normalizes reference label, labels are case-insensitive and whitespace-collapsed.
+fn normalize_label(label: &str) -> String {
+  label.split_whitespace().collect::<Vec<&str>>().join(" ").to_lowercase()
+}
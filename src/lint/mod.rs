@@ -0,0 +1,26 @@
+//! # Linters
+//!
+//! Offline checks of documentation and metadata files.
+
+pub mod manifest;
+pub mod markdown;
+
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// Problem reported by a linter.
+pub struct Diagnostic {
+  /// Name of the checked file.
+  pub file_name: PathBuf,
+  /// Line number, one-based.
+  pub line: usize,
+  /// Description of the problem.
+  pub message: String,
+}
+
+impl Display for Diagnostic {
+  /// Implementation of [Display] trait for [Diagnostic].
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}:{}: {}", self.file_name.display(), self.line, self.message)
+  }
+}
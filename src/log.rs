@@ -0,0 +1,92 @@
+//! # Diagnostic output
+//!
+//! Warnings, detailed reports and progress are written to standard error,
+//! filtered by the verbosity level selected with `-q`, `-v` and `-vv` options,
+//! so the standard output contains only the result of the command.
+
+use antex::{StyledText, auto};
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Width of the progress bar in characters.
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+/// Verbosity level of the diagnostic output.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+  /// Only errors are reported.
+  Quiet,
+  /// Errors, warnings and progress are reported.
+  Normal,
+  /// Executed commands and detailed reports are reported additionally.
+  Verbose,
+  /// Debugging details, like durations of commands, are reported additionally.
+  Debug,
+}
+
+/// Current verbosity level.
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Normal as u8);
+
+/// Sets the verbosity level.
+pub fn set_level(level: Level) {
+  LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Returns `true` when messages of the specified level are reported.
+pub fn enabled(level: Level) -> bool {
+  LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Reports the warning.
+pub fn warning(message: impl ToString) {
+  if enabled(Level::Normal) {
+    clear_progress();
+    eprintln!("{}", auto().bold().yellow().s("warning").reset().s(": ").s(message.to_string()));
+  }
+}
+
+/// Reports the informational message.
+pub fn info(message: impl AsRef<str>) {
+  if enabled(Level::Normal) {
+    clear_progress();
+    eprintln!("{}", message.as_ref());
+  }
+}
+
+/// Reports the message in verbose mode.
+pub fn verbose(message: impl AsRef<str>) {
+  if enabled(Level::Verbose) {
+    clear_progress();
+    eprintln!("{}", message.as_ref());
+  }
+}
+
+/// Reports the message in debug mode.
+pub fn debug(message: impl AsRef<str>) {
+  if enabled(Level::Debug) {
+    clear_progress();
+    eprintln!("{}", auto().bright_black().s("debug").reset().s(": ").s(message.as_ref()));
+  }
+}
+
+/// Displays the progress bar with the number of completed steps, only when standard error is a terminal.
+pub fn progress(current: usize, total: usize, message: &str) {
+  if !enabled(Level::Normal) || enabled(Level::Verbose) || !std::io::stderr().is_terminal() {
+    return;
+  }
+  let total = total.max(1);
+  let filled = PROGRESS_BAR_WIDTH * current.min(total) / total;
+  let bar = format!("{}{}", "█".repeat(filled), "░".repeat(PROGRESS_BAR_WIDTH - filled));
+  let mut stderr = std::io::stderr();
+  _ = write!(stderr, "\r\x1b[2K{bar} {current}/{total} {message}");
+  _ = stderr.flush();
+}
+
+/// Clears the progress bar.
+pub fn clear_progress() {
+  if enabled(Level::Normal) && !enabled(Level::Verbose) && std::io::stderr().is_terminal() {
+    let mut stderr = std::io::stderr();
+    _ = write!(stderr, "\r\x1b[2K");
+    _ = stderr.flush();
+  }
+}
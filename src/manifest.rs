@@ -0,0 +1,276 @@
+//! # Cargo manifest loader
+//!
+//! Loads the package manifest and resolves the fields inherited
+//! from the `[workspace.package]` table of the workspace manifest,
+//! like `version.workspace = true` or `license.workspace = true`.
+
+use crate::errors::*;
+use crate::utils::{absolute, parse_toml};
+use std::path::{Path, PathBuf};
+
+/// Name of the Cargo manifest file.
+pub const MANIFEST_FILE_NAME: &str = "Cargo.toml";
+
+/// Package manifest with inherited fields resolved.
+pub struct Manifest {
+  /// Directory containing the package manifest.
+  dir: PathBuf,
+  /// Parsed package manifest.
+  parsed: toml::Value,
+  /// Workspace the package belongs to.
+  workspace: Option<Workspace>,
+}
+
+/// Workspace manifest.
+pub struct Workspace {
+  /// Directory containing the workspace manifest.
+  dir: PathBuf,
+  /// Parsed workspace manifest.
+  parsed: toml::Value,
+}
+
+impl Manifest {
+  /// Loads the package manifest from the specified directory.
+  pub fn load(dir: impl AsRef<Path>) -> Result<Self> {
+    let dir = dir.as_ref().to_path_buf();
+    let mut parsed = parse_manifest(dir.join(MANIFEST_FILE_NAME))?;
+    let Some(toml::Value::Table(package)) = parsed.get_mut("package") else {
+      return Err(MaggError::manifest(format!(
+        "[package] section not found in {}, use --workspace or --package for virtual manifests",
+        dir.join(MANIFEST_FILE_NAME).display()
+      )));
+    };
+    let inherited = package
+      .iter()
+      .filter(|(_, value)| value.get("workspace").and_then(|workspace| workspace.as_bool()) == Some(true))
+      .map(|(key, _)| key.clone())
+      .collect::<Vec<String>>();
+    let workspace = Workspace::find(&dir).ok();
+    if !inherited.is_empty() {
+      let Some(workspace) = &workspace else {
+        return Err(MaggError::manifest(format!("workspace manifest not found for directory: {}", dir.display())));
+      };
+      for key in inherited {
+        let value = workspace
+          .package(&key)
+          .ok_or_else(|| MaggError::manifest(format!("workspace.package.{key} not found in {}", workspace.dir.join(MANIFEST_FILE_NAME).display())))?;
+        package.insert(key, value.clone());
+      }
+    }
+    Ok(Self { dir, parsed, workspace })
+  }
+
+  /// Returns the directory containing the package manifest.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// Returns the package name.
+  pub fn name(&self) -> Result<&str> {
+    self.package_str("name")?.ok_or_else(|| MaggError::manifest("package.name not found in Cargo.toml"))
+  }
+
+  /// Returns the magg configuration value with the specified dotted key, like `readme.translations`.
+  ///
+  /// The value is searched in `[package.metadata.magg]` table first,
+  /// and then in `[workspace.metadata.magg]` table of the workspace manifest.
+  pub fn metadata(&self, key: &str) -> Option<&toml::Value> {
+    fn lookup<'a>(table: Option<&'a toml::Value>, key: &str) -> Option<&'a toml::Value> {
+      let magg = table.and_then(|table| table.get("metadata")).and_then(|metadata| metadata.get("magg"));
+      key.split('.').fold(magg, |value, key| value.and_then(|value| value.get(key)))
+    }
+    lookup(self.parsed.get("package"), key).or_else(|| self.workspace.as_ref().and_then(|workspace| lookup(workspace.parsed.get("workspace"), key)))
+  }
+
+  /// Returns the magg configuration string with the specified dotted key.
+  pub fn metadata_str(&self, key: &str) -> Result<Option<&str>> {
+    match self.metadata(key) {
+      None => Ok(None),
+      Some(toml::Value::String(value)) => Ok(Some(value)),
+      Some(_) => Err(MaggError::manifest(format!("metadata.magg.{key} in Cargo.toml must be a string"))),
+    }
+  }
+
+  /// Returns the value of the optional field of the package.
+  pub fn package(&self, key: &str) -> Option<&toml::Value> {
+    self.parsed.get("package").and_then(|package| package.get(key))
+  }
+
+  /// Returns the e-mail address of the first package author having one.
+  pub fn author_email(&self) -> Option<String> {
+    self
+      .package("authors")?
+      .as_array()?
+      .iter()
+      .filter_map(|author| author.as_str())
+      .find_map(|author| Some(author.split_once('<')?.1.split_once('>')?.0.trim().to_string()))
+  }
+
+  /// Returns the value of the optional string field of the package.
+  pub fn package_str(&self, key: &str) -> Result<Option<&str>> {
+    match self.package(key) {
+      None => Ok(None),
+      Some(toml::Value::String(value)) => Ok(Some(value)),
+      Some(_) => Err(MaggError::manifest(format!("package.{key} in Cargo.toml must be a string"))),
+    }
+  }
+}
+
+impl Workspace {
+  /// Returns the directory containing the workspace manifest.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// Searches the specified directory and its ancestors for the workspace manifest.
+  pub fn find(dir: impl AsRef<Path>) -> Result<Self> {
+    let dir = absolute(dir)?;
+    for ancestor in dir.ancestors() {
+      let manifest_path = ancestor.join(MANIFEST_FILE_NAME);
+      if manifest_path.exists() {
+        let parsed = parse_manifest(&manifest_path)?;
+        if parsed.get("workspace").is_some() {
+          return Ok(Self {
+            dir: ancestor.to_path_buf(),
+            parsed,
+          });
+        }
+      }
+    }
+    Err(MaggError::manifest(format!("workspace manifest not found for directory: {}", dir.display())))
+  }
+
+  /// Returns the field of the `[workspace.package]` table.
+  pub fn package(&self, key: &str) -> Option<&toml::Value> {
+    self
+      .parsed
+      .get("workspace")
+      .and_then(|workspace| workspace.get("package"))
+      .and_then(|package| package.get(key))
+  }
+
+  /// Returns the directories of all workspace members, in the order of declaration.
+  pub fn members(&self) -> Result<Vec<PathBuf>> {
+    let workspace = self.parsed.get("workspace");
+    let patterns = |key: &str| -> Vec<String> {
+      workspace
+        .and_then(|workspace| workspace.get(key))
+        .and_then(|value| value.as_array())
+        .map(|array| array.iter().filter_map(|value| value.as_str()).map(|value| value.to_string()).collect())
+        .unwrap_or_default()
+    };
+    let excluded = patterns("exclude").iter().map(|pattern| self.dir.join(pattern)).collect::<Vec<PathBuf>>();
+    let mut members = vec![];
+    if self.parsed.get("package").is_some() {
+      members.push(self.dir.clone());
+    }
+    for pattern in patterns("members") {
+      for dir in expand(&self.dir, &pattern) {
+        if dir.join(MANIFEST_FILE_NAME).exists() && !excluded.contains(&dir) && !members.contains(&dir) {
+          members.push(dir);
+        }
+      }
+    }
+    Ok(members)
+  }
+}
+
+/// Returns the package directories selected by `--workspace` and `--package` options.
+///
+/// When no packages are selected, the specified directory is returned.
+/// Member directories below the specified directory are returned relative to it.
+pub fn select_packages(dir: impl AsRef<Path>, workspace: bool, packages: &[String]) -> Result<Vec<PathBuf>> {
+  let dir = dir.as_ref();
+  if !workspace && packages.is_empty() {
+    return Ok(vec![dir.to_path_buf()]);
+  }
+  let base_dir = absolute(dir)?;
+  let members = match Workspace::find(dir) {
+    Ok(workspace) => workspace.members()?,
+    Err(_) => vec![base_dir.clone()],
+  };
+  let members = members
+    .into_iter()
+    .map(|member| match member.strip_prefix(&base_dir) {
+      Ok(relative) => dir.join(relative),
+      Err(_) => member,
+    })
+    .collect::<Vec<PathBuf>>();
+  if workspace {
+    return Ok(members);
+  }
+  let mut selected = vec![];
+  for package in packages {
+    let mut found = false;
+    for member in &members {
+      if Manifest::load(member)?.name()? == package {
+        selected.push(member.clone());
+        found = true;
+      }
+    }
+    if !found {
+      return Err(MaggError::validation(format!("package '{package}' not found in workspace")));
+    }
+  }
+  Ok(selected)
+}
+
+/// Parses the manifest file, failures to read or parse it are reported as manifest errors.
+fn parse_manifest(path: impl AsRef<Path>) -> Result<toml::Value> {
+  parse_toml(path).map_err(|error| match error {
+    MaggError::Io { message, source } => MaggError::manifest(message).with_source(source),
+    MaggError::Parse { message, source } => MaggError::Manifest { message, source },
+    error => error,
+  })
+}
+
+/// Expands the workspace member pattern, wildcards `*` and `?` are supported in any path component.
+fn expand(root: &Path, pattern: &str) -> Vec<PathBuf> {
+  let mut paths = vec![root.to_path_buf()];
+  for component in pattern.split('/').filter(|component| !component.is_empty() && *component != ".") {
+    let mut expanded = vec![];
+    for path in paths {
+      if component.contains(['*', '?']) {
+        let Ok(entries) = std::fs::read_dir(&path) else {
+          continue;
+        };
+        let mut matching = entries
+          .filter_map(|entry| entry.ok())
+          .filter(|entry| entry.path().is_dir() && matches(component, &entry.file_name().to_string_lossy()))
+          .map(|entry| entry.path())
+          .collect::<Vec<PathBuf>>();
+        matching.sort();
+        expanded.append(&mut matching);
+      } else {
+        expanded.push(path.join(component));
+      }
+    }
+    paths = expanded;
+  }
+  paths
+}
+
+/// Matches the name against the pattern containing wildcards `*` and `?`.
+fn matches(pattern: &str, name: &str) -> bool {
+  let pattern = pattern.chars().collect::<Vec<char>>();
+  let name = name.chars().collect::<Vec<char>>();
+  let (mut p, mut n) = (0, 0);
+  let (mut star, mut mark) = (None, 0);
+  while n < name.len() {
+    if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+      p += 1;
+      n += 1;
+    } else if p < pattern.len() && pattern[p] == '*' {
+      star = Some(p);
+      mark = n;
+      p += 1;
+    } else if let Some(star) = star {
+      p = star + 1;
+      mark += 1;
+      n = mark;
+    } else {
+      return false;
+    }
+  }
+  pattern[p..].iter().all(|ch| *ch == '*')
+}
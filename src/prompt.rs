@@ -0,0 +1,69 @@
+//! # Interactive prompts
+//!
+//! Questions are written to the standard error, so the standard output contains only the results of commands.
+//! Missing values are asked for only when the standard input is a terminal, unless the interactive mode
+//! is requested explicitly, like in `magg changelog -i`, then answers are read from the standard input.
+
+use crate::errors::*;
+use std::io::{BufRead, IsTerminal, Write};
+
+/// Returns `true` when the user can answer questions.
+pub fn is_interactive() -> bool {
+  std::io::stdin().is_terminal()
+}
+
+/// Asks the question and returns the answer, or the default value when the answer is empty.
+pub fn ask(question: &str, default: Option<&str>) -> Result<String> {
+  let mut stderr = std::io::stderr();
+  match default {
+    Some(default) if !default.is_empty() => _ = write!(stderr, "{question} [{default}]: "),
+    _ => _ = write!(stderr, "{question}: "),
+  }
+  let answer = read_answer()?;
+  Ok(if answer.is_empty() { default.unwrap_or_default().to_string() } else { answer })
+}
+
+/// Returns the value when specified, otherwise asks the question in interactive mode,
+/// or returns the default value in non-interactive mode.
+pub fn value_or_ask(value: Option<String>, question: &str, default: Option<String>) -> Result<Option<String>> {
+  match value {
+    Some(value) => Ok(Some(value)),
+    None if is_interactive() => Ok(Some(ask(question, default.as_deref())?).filter(|answer| !answer.is_empty())),
+    None => Ok(default),
+  }
+}
+
+/// Asks to select one of the choices listed with numbers, returns the selected choice,
+/// the default value when the answer is empty, or the answer itself when it is not a number of any choice.
+pub fn select(question: &str, choices: &[String], default: Option<&str>) -> Result<String> {
+  let mut stderr = std::io::stderr();
+  for (index, choice) in choices.iter().enumerate() {
+    _ = writeln!(stderr, "  {}) {choice}", index + 1);
+  }
+  let answer = ask(question, default)?;
+  Ok(match answer.parse::<usize>() {
+    Ok(number) if (1..=choices.len()).contains(&number) => choices[number - 1].clone(),
+    _ => answer,
+  })
+}
+
+/// Asks the yes/no question, returns the default answer when the answer is empty.
+pub fn confirm(question: &str, default: bool) -> Result<bool> {
+  loop {
+    _ = write!(std::io::stderr(), "{question} [{}]: ", if default { "Y/n" } else { "y/N" });
+    match read_answer()?.to_lowercase().as_str() {
+      "" => return Ok(default),
+      "y" | "yes" => return Ok(true),
+      "n" | "no" => return Ok(false),
+      _ => {}
+    }
+  }
+}
+
+/// Reads the trimmed answer from the standard input, the answer is empty at the end of the input.
+fn read_answer() -> Result<String> {
+  _ = std::io::stderr().flush();
+  let mut answer = String::new();
+  std::io::stdin().lock().read_line(&mut answer).map_err(|e| MaggError::io("failed to read the answer", e))?;
+  Ok(answer.trim().to_string())
+}
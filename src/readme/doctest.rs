@@ -0,0 +1,146 @@
+//! # README.md examples tester
+//!
+//! Extracts Rust code blocks from the generated README.md file into a temporary
+//! crate and runs them as doctests against the scaffolded crate.
+
+use crate::errors::*;
+use crate::manifest::Manifest;
+use crate::utils;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Code block extracted from Markdown content.
+struct CodeBlock {
+  /// Info string following the opening fence, like `rust,no_run`.
+  info: String,
+  /// Line number of the opening fence, one-based.
+  line: usize,
+  /// Lines of code.
+  code: Vec<String>,
+}
+
+/// Tests Rust code blocks from the README.md content against the package described by the manifest.
+/// Returns the number of tested code blocks.
+pub fn test_examples(manifest: &Manifest, readme: &str) -> Result<usize> {
+  let package_name = manifest.name()?;
+  // Cargo assumes edition 2015 when the package does not specify one.
+  let edition = manifest.package_str("edition")?.unwrap_or("2015");
+  let blocks = extract_rust_blocks(readme)?;
+  if blocks.is_empty() {
+    return Ok(0);
+  }
+  let crate_dir = utils::absolute(manifest.dir())?;
+  // The temporary directory is unique for every run and removed when dropped.
+  let temp_dir = tempfile::Builder::new()
+    .prefix(&format!("magg-doctest-{package_name}-"))
+    .tempdir()
+    .map_err(|e| MaggError::io("failed to create temporary directory for README.md examples", e))?;
+  let doctest_dir = temp_dir.path();
+  write_doctest_crate(doctest_dir, &crate_dir, package_name, edition, &blocks)?;
+  let manifest_path = doctest_dir.join("Cargo.toml");
+  let target_dir = crate_dir.join("target").join("magg-doctest");
+  let status = std::process::Command::new("cargo")
+    .arg("test")
+    .arg("--doc")
+    .arg("--manifest-path")
+    .arg(&manifest_path)
+    .arg("--target-dir")
+    .arg(&target_dir)
+    .status()
+    .map_err(|e| error_spawn_command("cargo", e))?;
+  if status.success() {
+    Ok(blocks.len())
+  } else {
+    Err(MaggError::validation(format!("README.md examples failed to compile or run, status {status}")))
+  }
+}
+
+/// Writes the temporary doctest crate.
+fn write_doctest_crate(doctest_dir: &Path, crate_dir: &Path, package_name: &str, edition: &str, blocks: &[CodeBlock]) -> Result<()> {
+  let src_dir = doctest_dir.join("src");
+  std::fs::create_dir_all(&src_dir).map_err(|e| MaggError::io(format!("failed to create directory {}", src_dir.display()), e))?;
+  let mut manifest = String::new();
+  _ = writeln!(&mut manifest, "[package]");
+  _ = writeln!(&mut manifest, "name = \"{package_name}-readme-doctest\"");
+  _ = writeln!(&mut manifest, "version = \"0.0.0\"");
+  _ = writeln!(&mut manifest, "edition = {}", toml::Value::String(edition.to_string()));
+  _ = writeln!(&mut manifest, "publish = false");
+  _ = writeln!(&mut manifest);
+  _ = writeln!(&mut manifest, "[dependencies]");
+  _ = writeln!(
+    &mut manifest,
+    "{package_name} = {{ path = {} }}",
+    toml::Value::String(crate_dir.to_string_lossy().to_string())
+  );
+  _ = writeln!(&mut manifest);
+  _ = writeln!(&mut manifest, "[workspace]");
+  utils::write_file(doctest_dir.join("Cargo.toml"), &manifest)?;
+  let mut lib = String::new();
+  for block in blocks {
+    _ = writeln!(&mut lib, "/// README.md, line {}", block.line);
+    _ = writeln!(&mut lib, "///");
+    _ = writeln!(&mut lib, "/// ```{}", block.info);
+    for line in &block.code {
+      _ = writeln!(&mut lib, "/// {line}");
+    }
+    _ = writeln!(&mut lib, "/// ```");
+    _ = writeln!(&mut lib, "pub mod line_{} {{}}", block.line);
+    _ = writeln!(&mut lib);
+  }
+  utils::write_file(src_dir.join("lib.rs"), &lib)
+}
+
+/// Extracts fenced code blocks that are treated as Rust code by rustdoc.
+/// Reports an error when the last code block is not closed.
+fn extract_rust_blocks(content: &str) -> Result<Vec<CodeBlock>> {
+  let mut blocks = vec![];
+  let mut current: Option<(String, CodeBlock)> = None;
+  for (index, line) in content.lines().enumerate() {
+    let trimmed = line.trim_start();
+    match current.take() {
+      None => {
+        if let Some(fence) = opening_fence(trimmed) {
+          let info = trimmed[fence.len()..].trim().to_string();
+          current = Some((
+            fence,
+            CodeBlock {
+              info,
+              line: index + 1,
+              code: vec![],
+            },
+          ));
+        }
+      }
+      Some((fence, mut block)) => {
+        if trimmed.starts_with(&fence) && trimmed[fence.len()..].trim().is_empty() {
+          if is_rust(&block.info) {
+            blocks.push(block);
+          }
+        } else {
+          block.code.push(line.to_string());
+          current = Some((fence, block));
+        }
+      }
+    }
+  }
+  match current {
+    Some((_, block)) => Err(MaggError::parse(format!("unclosed code block in README.md, line {}", block.line))),
+    None => Ok(blocks),
+  }
+}
+
+/// Returns the opening fence of a code block, when the line starts one.
+fn opening_fence(line: &str) -> Option<String> {
+  for ch in ['`', '~'] {
+    let count = line.chars().take_while(|c| *c == ch).count();
+    if count >= 3 {
+      return Some(ch.to_string().repeat(count));
+    }
+  }
+  None
+}
+
+/// Returns `true` when the info string marks a Rust code block.
+fn is_rust(info: &str) -> bool {
+  info.split([',', ' ']).next().is_some_and(|language| language == "rust")
+}
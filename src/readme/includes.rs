@@ -0,0 +1,131 @@
+//! # Include directives
+//!
+//! Resolves mdBook-style include directives in the body of the README.md file:
+//!
+//! - `{{#include file}}` includes the whole file,
+//! - `{{#include file:5}}` includes the file starting from line 5,
+//! - `{{#include file::20}}` includes the file up to line 20,
+//! - `{{#include file:5:20}}` includes lines from 5 to 20,
+//! - `{{#include file:name}}` includes lines between `ANCHOR: name` and `ANCHOR_END: name`.
+//!
+//! Line numbers are one-based and inclusive. Included files are searched relative
+//! to the directory of the file containing the directive first, and then relative
+//! to the package directory (the crate root).
+
+use crate::errors::*;
+use crate::utils::read_file;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Pattern for matching include directives.
+const INCLUDE_PATTERN: &str = r#"\{\{#include\s+([^}\s]+)\s*}}"#;
+
+/// Regular expression for matching include directives.
+static RE_INCLUDE: LazyLock<Regex> = LazyLock::new(|| Regex::new(INCLUDE_PATTERN).unwrap());
+
+/// Pattern for matching anchor markers in included files.
+const ANCHOR_PATTERN: &str = r#"ANCHOR(_END)?:\s*([\w-]+)"#;
+
+/// Regular expression for matching anchor markers in included files.
+static RE_ANCHOR: LazyLock<Regex> = LazyLock::new(|| Regex::new(ANCHOR_PATTERN).unwrap());
+
+/// Part of the included file selected by the directive.
+enum Selection {
+  /// The whole file.
+  All,
+  /// Range of lines, one-based and inclusive.
+  Lines(Option<usize>, Option<usize>),
+  /// Lines between named anchors.
+  Anchor(String),
+}
+
+/// Replaces all include directives in the content of the file with the specified name.
+pub fn resolve_includes(content: &str, file_name: impl AsRef<Path>, root_dir: impl AsRef<Path>) -> Result<String> {
+  let base_dir = file_name.as_ref().parent().map(|dir| dir.to_path_buf()).unwrap_or_default();
+  let mut output = String::with_capacity(content.len());
+  let mut last = 0;
+  for captures in RE_INCLUDE.captures_iter(content) {
+    let directive = captures.get(0).unwrap();
+    output.push_str(&content[last..directive.start()]);
+    output.push_str(&include(&captures[1], &base_dir, root_dir.as_ref())?);
+    last = directive.end();
+  }
+  output.push_str(&content[last..]);
+  Ok(output)
+}
+
+/// Returns the selected content of the included file.
+fn include(argument: &str, base_dir: &Path, root_dir: &Path) -> Result<String> {
+  let (file_name, selection) = parse_argument(argument)?;
+  let path = locate(file_name, base_dir, root_dir);
+  let content = read_file(&path)?;
+  let lines = content.lines().collect::<Vec<&str>>();
+  let selected = match selection {
+    Selection::All => return Ok(content.trim_end_matches('\n').to_string()),
+    Selection::Lines(start, end) => {
+      let start = start.unwrap_or(1);
+      let end = end.unwrap_or(lines.len()).min(lines.len());
+      if start == 0 || start > end {
+        return Err(MaggError::parse(format!("invalid line range in include directive: {argument}")));
+      }
+      lines[start - 1..end].to_vec()
+    }
+    Selection::Anchor(name) => {
+      let mut selected = vec![];
+      let mut inside = false;
+      let mut found = false;
+      for line in lines {
+        if let Some(captures) = RE_ANCHOR.captures(line) {
+          if captures[2] == name {
+            found = true;
+            inside = captures.get(1).is_none();
+          }
+          // Lines with anchor markers are never included.
+          continue;
+        }
+        if inside {
+          selected.push(line);
+        }
+      }
+      if !found {
+        return Err(MaggError::validation(format!("anchor '{name}' not found in file: {}", path.display())));
+      }
+      selected
+    }
+  };
+  Ok(selected.join("\n"))
+}
+
+/// Splits the directive argument into the file name and the selection.
+fn parse_argument(argument: &str) -> Result<(&str, Selection)> {
+  let mut parts = argument.splitn(3, ':');
+  let file_name = parts.next().unwrap_or_default();
+  let selection = match (parts.next(), parts.next()) {
+    (None, _) => Selection::All,
+    (Some(first), None) => match first.parse::<usize>() {
+      Ok(start) => Selection::Lines(Some(start), None),
+      Err(_) if !first.is_empty() => Selection::Anchor(first.to_string()),
+      Err(_) => Selection::All,
+    },
+    (Some(first), Some(second)) => Selection::Lines(parse_line_number(first, argument)?, parse_line_number(second, argument)?),
+  };
+  Ok((file_name, selection))
+}
+
+/// Parses an optional line number in the directive argument.
+fn parse_line_number(value: &str, argument: &str) -> Result<Option<usize>> {
+  if value.is_empty() {
+    return Ok(None);
+  }
+  value
+    .parse::<usize>()
+    .map(Some)
+    .map_err(|_| MaggError::parse(format!("invalid line number '{value}' in include directive: {argument}")))
+}
+
+/// Returns the path to the included file.
+fn locate(file_name: &str, base_dir: &Path, root_dir: &Path) -> PathBuf {
+  let path = base_dir.join(file_name);
+  if path.exists() { path } else { root_dir.join(file_name) }
+}
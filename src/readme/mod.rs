@@ -0,0 +1,268 @@
+//! # README.md file generator
+
+mod doctest;
+mod includes;
+mod translation;
+
+use crate::code_of_conduct::CodeOfConduct;
+use crate::errors::*;
+use crate::licenses::{self, Expression};
+use crate::manifest::Manifest;
+use crate::repository::Repository;
+use crate::utils::read_file;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use translation::{DEFAULT_LANGUAGE, Translation};
+
+pub use doctest::test_examples;
+
+const TWO_SPACES: &str = "  ";
+
+const LICENSE_COLOR: &str = "4169E1";
+const HUMAN_COLOR: &str = "DC143C";
+const ENGOS_COLOR: &str = "32CD32";
+
+/// Options of the README generator.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+  /// Names of the files containing the body text, one per language.
+  bodies: Vec<String>,
+  /// Directory of generated README files, when different from the package directory.
+  output_dir: Option<PathBuf>,
+}
+
+impl Options {
+  /// Creates options without body files.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds the file containing the body text, relative to the package directory.
+  ///
+  /// The language of the body file is taken from its name, like `README.de.md`,
+  /// names without the language denote English.
+  pub fn body(mut self, file_name: impl Into<String>) -> Self {
+    self.bodies.push(file_name.into());
+    self
+  }
+
+  /// Sets the directory of generated README files, the package directory by default.
+  ///
+  /// The coverage badge of existing README files in this directory is kept.
+  pub fn output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+    self.output_dir = Some(dir.into());
+    self
+  }
+}
+
+/// Scaffolds README files for the package described by the manifest, one file per body file.
+///
+/// The first body file is scaffolded into `README.md`, the remaining ones into `README.<language>.md` files.
+/// Returns the names of scaffolded files together with their contents.
+pub fn scaffold_readmes(manifest: &Manifest, options: &Options) -> Result<Vec<(String, String)>> {
+  let mut translations = vec![];
+  for (index, file_name) in options.bodies.iter().enumerate() {
+    let code = Path::new(file_name)
+      .file_stem()
+      .and_then(|stem| Path::new(stem).extension())
+      .map(|code| code.to_string_lossy().to_string())
+      .unwrap_or(DEFAULT_LANGUAGE.to_string());
+    let output_file_name = if index == 0 { "README.md".to_string() } else { format!("README.{code}.md") };
+    translations.push((file_name, output_file_name, Translation::load(manifest, &code)?));
+  }
+  // Prepare the language switcher, when there are multiple languages.
+  let switcher = |current: &str| -> String {
+    if translations.len() < 2 {
+      return String::new();
+    }
+    let links = translations
+      .iter()
+      .map(|(_, output_file_name, translation)| {
+        if output_file_name == current {
+          format!("**{}**", translation.language)
+        } else {
+          format!("[{}]({})", translation.language, output_file_name)
+        }
+      })
+      .collect::<Vec<String>>();
+    format!("{}\n\n", links.join(" | "))
+  };
+  let mut readmes = vec![];
+  let output_dir = options.output_dir.as_deref().unwrap_or(manifest.dir());
+  for (file_name, output_file_name, translation) in &translations {
+    let contents = scaffold_readme(manifest, file_name, &output_dir.join(output_file_name), translation, &switcher(output_file_name))?;
+    readmes.push((output_file_name.clone(), contents));
+  }
+  Ok(readmes)
+}
+
+/// Returns the link to the coverage badge, the link in the existing README file is kept,
+/// because it is updated by `coverio` after the README file is generated.
+fn coverage_badge(file_name: &Path) -> String {
+  read_file(file_name)
+    .ok()
+    .and_then(|contents| contents.lines().find(|line| line.starts_with("[cov-badge]: ")).map(|line| line.to_string()))
+    .unwrap_or("[cov-badge]: https://img.shields.io/badge/coverage-0%25-21b577.svg".to_string())
+}
+
+/// Scaffolds README file with fixed sections in the specified translation.
+fn scaffold_readme(manifest: &Manifest, file_name: impl AsRef<Path>, output_path: &Path, translation: &Translation, switcher: &str) -> Result<String> {
+  let mut output = String::new();
+  let file_name = manifest.dir().join(file_name);
+  let body = includes::resolve_includes(&read_file(&file_name)?, &file_name, manifest.dir())?;
+  let package_name = manifest.name()?;
+  let repository = Repository::resolve(manifest)?;
+  let code_of_conduct = CodeOfConduct::resolve(manifest)?;
+  let license = manifest
+    .package_str("license")?
+    .ok_or_else(|| MaggError::manifest("package.license not found in Cargo.toml"))?;
+  let expression = Expression::parse(license)?;
+  let licenses = package_licenses(&expression);
+  // Write the name of the package.
+  _ = writeln!(&mut output, "### {}", package_name);
+  _ = writeln!(&mut output);
+  // Write the language switcher.
+  _ = write!(&mut output, "{switcher}");
+  // Write badges.
+  _ = writeln!(&mut output, "[![crates.io][crates-badge]][crates-url]");
+  _ = writeln!(&mut output, "[![coverage][cov-badge]][cov-url]{TWO_SPACES}");
+  _ = writeln!(&mut output, "![build Linux][build-badge-linux]");
+  _ = writeln!(&mut output, "![build Windows][build-badge-windows]");
+  _ = writeln!(&mut output, "![build macOs][build-badge-macos]");
+  _ = writeln!(&mut output, "![build macOs arm64][build-badge-macos-arm64]{TWO_SPACES}");
+  for license in &licenses {
+    _ = writeln!(&mut output, "[![{label}-license][{label}-badge]][{label}-license-url]", label = license.label);
+  }
+  _ = writeln!(&mut output, "[![cc][cc-badge]][cc-url]{TWO_SPACES}");
+  _ = writeln!(&mut output, "[![mbh][mbh-badge]][mbh-url]");
+  _ = writeln!(&mut output, "[![es][es-badge]][es-url]\n");
+  // Write links to badges and files.
+  _ = writeln!(&mut output, "[crates-badge]: https://img.shields.io/crates/v/{package_name}.svg");
+  _ = writeln!(&mut output, "[crates-url]: https://crates.io/crates/{package_name}");
+  _ = writeln!(&mut output, "{}", coverage_badge(output_path));
+  _ = writeln!(&mut output, "[cov-url]: https://crates.io/crates/coverio");
+  _ = writeln!(&mut output, "[build-badge-linux]: {}", repository.workflow_badge_url("build-linux.yml"));
+  _ = writeln!(&mut output, "[build-badge-windows]: {}", repository.workflow_badge_url("build-windows.yml"));
+  _ = writeln!(&mut output, "[build-badge-macos]: {}", repository.workflow_badge_url("build-macos.yml"));
+  _ = writeln!(&mut output, "[build-badge-macos-arm64]: {}", repository.workflow_badge_url("build-macos-arm64.yml"));
+  for license in &licenses {
+    let label = &license.label;
+    _ = writeln!(
+      &mut output,
+      "[{label}-badge]: https://img.shields.io/badge/License-{}-{LICENSE_COLOR}.svg",
+      badge_text(&license.id)
+    );
+    _ = writeln!(&mut output, "[{label}-url]: {}", license.url);
+    for (file_label, file_name) in &license.files {
+      _ = writeln!(&mut output, "[{file_label}]: {}", repository.blob_url(file_name));
+    }
+  }
+  _ = writeln!(
+    &mut output,
+    "[cc-badge]: https://img.shields.io/badge/{}-{}-{LICENSE_COLOR}.svg",
+    badge_text(code_of_conduct.badge_label),
+    badge_text(code_of_conduct.badge_message)
+  );
+  _ = writeln!(&mut output, "[cc-url]: {}", repository.blob_url("CODE_OF_CONDUCT.md"));
+  _ = writeln!(&mut output, "[mbh-badge]: https://img.shields.io/badge/Made_by_a-HUMAN-{HUMAN_COLOR}.svg");
+  _ = writeln!(&mut output, "[mbh-url]: https://github.com/DariuszDepta");
+  _ = writeln!(&mut output, "[es-badge]: https://img.shields.io/badge/at-Engos_Software-{ENGOS_COLOR}.svg");
+  _ = writeln!(&mut output, "[es-url]: https://engos.de");
+  _ = writeln!(&mut output, "[repository-url]: {repository}");
+  // Write the content.
+  _ = writeln!(&mut output);
+  _ = write!(&mut output, "{body}");
+  _ = writeln!(&mut output);
+  // Write license section.
+  _ = writeln!(&mut output, "{}\n\n{}\n", translation.license, license_terms(&expression, license, &licenses, translation));
+  // Write contribution section.
+  _ = write!(&mut output, "{}", translation.contribution.replace("[PACKAGE_NAME]", package_name));
+  Ok(output)
+}
+
+/// License of the package, presented in badges and in the license section.
+struct License {
+  /// SPDX license identifier, followed by the exception, like `Apache-2.0 WITH LLVM-exception`.
+  id: String,
+  /// Full name of the license.
+  name: String,
+  /// URL of the license text published by SPDX.
+  url: String,
+  /// Prefix of link labels, like `mit` in `[mit-url]`.
+  label: String,
+  /// Link labels and names of license files.
+  files: Vec<(String, String)>,
+}
+
+/// Returns licenses used in the license expression, with the same license files as generated by the `licenses` command.
+fn package_licenses(expression: &Expression) -> Vec<License> {
+  licenses::license_file_names(expression)
+    .into_iter()
+    .map(|(license_id, exception_id, file_names)| {
+      // Labels of the MIT and Apache licenses are kept short, like in existing README files.
+      let mut label = match license_id.to_lowercase().as_str() {
+        "apache-2.0" => "apache".to_string(),
+        other => other.to_string(),
+      };
+      let mut id = license_id.to_string();
+      let mut name = licenses::license_name(license_id).unwrap_or(license_id).to_string();
+      if let Some(exception_id) = exception_id {
+        label = format!("{label}-{}", exception_id.to_lowercase());
+        id = format!("{id} WITH {exception_id}");
+        name = format!("{name} with {exception_id}");
+      }
+      let files = file_names
+        .into_iter()
+        .enumerate()
+        .map(|(index, file_name)| {
+          let kind = if index == 0 { "license".to_string() } else { file_name.to_lowercase() };
+          (format!("{label}-{kind}-url"), file_name)
+        })
+        .collect();
+      License {
+        id,
+        name,
+        url: format!("https://spdx.org/licenses/{license_id}.html"),
+        label,
+        files,
+      }
+    })
+    .collect()
+}
+
+/// Returns the translated license terms, worded after the structure of the license expression.
+fn license_terms(expression: &Expression, license: &str, licenses: &[License], translation: &Translation) -> String {
+  /// Returns `true` when all licenses are joined with the same operator, `OR` or `AND`.
+  fn joined(expression: &Expression, or: bool) -> bool {
+    match expression {
+      Expression::License(..) => true,
+      Expression::Or(left, right) if or => joined(left, or) && joined(right, or),
+      Expression::And(left, right) if !or => joined(left, or) && joined(right, or),
+      _ => false,
+    }
+  }
+  let items = licenses
+    .iter()
+    .map(|license| {
+      let files = license
+        .files
+        .iter()
+        .map(|(file_label, file_name)| format!("[{file_name}][{file_label}]"))
+        .collect::<Vec<String>>()
+        .join(&format!(" {} ", translation.license_and));
+      format!("[{}][{}-url] ({} {files})", license.name, license.label, translation.license_see)
+    })
+    .collect::<Vec<String>>();
+  let list = items.iter().map(|item| format!("- {item}")).collect::<Vec<String>>().join("\n");
+  match expression {
+    Expression::License(..) => translation.license_one.replace("[LICENSE]", &items[0]),
+    Expression::Or(..) if joined(expression, true) => translation.license_any.replace("[LICENSES]", &list),
+    Expression::And(..) if joined(expression, false) => translation.license_all.replace("[LICENSES]", &list),
+    _ => translation.license_expression.replace("[EXPRESSION]", license).replace("[LICENSES]", &list),
+  }
+}
+
+/// Escapes the text of the badge, as required by shields.io.
+fn badge_text(text: &str) -> String {
+  text.replace('-', "--").replace('_', "__").replace(' ', "%20")
+}
@@ -0,0 +1,105 @@
+//! # Translations of fixed README.md sections
+//!
+//! Translations are bundled for English and German. Any translation key can be overridden
+//! by a file named `<language>.toml` placed in the directory configured in `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.magg.readme]
+//! translations = "docs/i18n"
+//! ```
+
+use crate::errors::*;
+use crate::manifest::Manifest;
+use std::path::Path;
+
+/// Default language of the README.md file.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Bundled translations, keyed by language code.
+const BUNDLED: [(&str, &str); 2] = [("en", include_str!("i18n/en.toml")), ("de", include_str!("i18n/de.toml"))];
+
+/// Translation keys, all are required.
+const KEYS: [&str; 9] = [
+  "language",
+  "license",
+  "license_one",
+  "license_any",
+  "license_all",
+  "license_expression",
+  "license_see",
+  "license_and",
+  "contribution",
+];
+
+/// Translation of fixed README.md sections.
+pub struct Translation {
+  /// Name of the language in this language, used in the language switcher.
+  pub language: String,
+  /// Heading of the license section.
+  pub license: String,
+  /// License terms of a single license, `[LICENSE]` is replaced with the license.
+  pub license_one: String,
+  /// License terms of alternative licenses, `[LICENSES]` is replaced with the list of licenses.
+  pub license_any: String,
+  /// License terms of licenses applying together, `[LICENSES]` is replaced with the list of licenses.
+  pub license_all: String,
+  /// License terms of other license expressions, `[EXPRESSION]` is replaced with the expression
+  /// and `[LICENSES]` with the list of licenses.
+  pub license_expression: String,
+  /// Word introducing license files of a license, like `see`.
+  pub license_see: String,
+  /// Word joining license files of a license, like `and`.
+  pub license_and: String,
+  /// Contribution section.
+  pub contribution: String,
+}
+
+impl Translation {
+  /// Loads the translation for the specified language, bundled translation is overridden
+  /// with the content of the file from the directory configured in package metadata.
+  pub fn load(manifest: &Manifest, code: &str) -> Result<Self> {
+    let mut table = match BUNDLED.iter().find(|(bundled, _)| *bundled == code) {
+      Some((_, content)) => parse(content, Path::new("bundled translation"))?,
+      None => toml::Table::new(),
+    };
+    if let Some(dir) = manifest.metadata_str("readme.translations")? {
+      let path = manifest.dir().join(dir).join(format!("{code}.toml"));
+      if path.exists() {
+        table.extend(parse(&crate::utils::read_file(&path)?, &path)?);
+      }
+    }
+    let get = |key: &str| -> Result<String> {
+      match table.get(key) {
+        Some(toml::Value::String(value)) => Ok(value.clone()),
+        _ => Err(MaggError::validation(format!("translation key '{key}' not found for language: {code}"))),
+      }
+    };
+    let [
+      language,
+      license,
+      license_one,
+      license_any,
+      license_all,
+      license_expression,
+      license_see,
+      license_and,
+      contribution,
+    ] = KEYS.map(get);
+    Ok(Self {
+      language: language?,
+      license: license?,
+      license_one: license_one?,
+      license_any: license_any?,
+      license_all: license_all?,
+      license_expression: license_expression?,
+      license_see: license_see?,
+      license_and: license_and?,
+      contribution: contribution?,
+    })
+  }
+}
+
+/// Parses the translation file.
+fn parse(content: &str, path: &Path) -> Result<toml::Table> {
+  toml::from_str(content).map_err(|e| MaggError::parse(format!("failed to parse translation file {}", path.display())).with_source(e))
+}
@@ -0,0 +1,403 @@
+//! # Release publisher
+//!
+//! Creates or updates the release of the tag on the forge hosting the repository,
+//! using the REST API of GitHub, GitLab or Gitea. Release notes are replaced,
+//! tags with semver pre-release versions are marked as prereleases (except on GitLab, which has no such flag)
+//! and files are attached, replacing previously attached files with the same names.
+
+use crate::errors::*;
+use crate::repository::{Forge, Repository};
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Timeout of requests sent to the forge.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Boundary separating parts of multipart requests.
+const MULTIPART_BOUNDARY: &str = "magg-release-asset-7f3a9c2e5b8d4f10";
+
+/// Options of the release publisher.
+pub struct Options {
+  /// Repository URL, like `https://github.com/EngosSoftware/magg`.
+  repository: String,
+  /// Tag of the release.
+  tag: String,
+  /// Release notes in Markdown.
+  notes: String,
+  /// Paths of files attached to the release.
+  assets: Vec<PathBuf>,
+  /// Base URL of the forge REST API, derived from the repository URL when not specified.
+  api_url: Option<String>,
+  /// Authentication token, taken from the environment when not specified.
+  token: Option<String>,
+}
+
+impl Options {
+  /// Creates options for publishing the release of the tag in the repository with the specified URL.
+  pub fn new(repository: impl Into<String>, tag: impl Into<String>) -> Self {
+    Self {
+      repository: repository.into(),
+      tag: tag.into(),
+      notes: String::new(),
+      assets: vec![],
+      api_url: None,
+      token: None,
+    }
+  }
+
+  /// Sets the release notes.
+  pub fn notes(mut self, notes: impl Into<String>) -> Self {
+    self.notes = notes.into();
+    self
+  }
+
+  /// Attaches the file to the release.
+  pub fn asset(mut self, path: impl Into<PathBuf>) -> Self {
+    self.assets.push(path.into());
+    self
+  }
+
+  /// Sets the base URL of the forge REST API, like `https://api.github.com`.
+  pub fn api_url(mut self, api_url: impl Into<String>) -> Self {
+    self.api_url = Some(api_url.into());
+    self
+  }
+
+  /// Sets the authentication token, by default the token is taken from `GITHUB_TOKEN`, `GH_TOKEN`
+  /// or `gh auth token` for GitHub, from `GITLAB_TOKEN` for GitLab and from `GITEA_TOKEN` for Gitea.
+  pub fn token(mut self, token: impl Into<String>) -> Self {
+    self.token = Some(token.into());
+    self
+  }
+}
+
+/// Published release.
+#[derive(Debug, Clone)]
+pub struct Release {
+  /// Tag of the release.
+  pub tag: String,
+  /// URL of the release page.
+  pub url: String,
+  /// Flag indicating if the release was created, otherwise an existing release was updated.
+  pub created: bool,
+  /// Flag indicating if the release is marked as a prerelease, always `false` on GitLab.
+  pub prerelease: bool,
+  /// Names of attached files.
+  pub assets: Vec<String>,
+}
+
+/// Creates or updates the release and attaches files to it.
+pub fn publish(options: &Options) -> Result<Release> {
+  let repository = Repository::parse(&options.repository)?;
+  // All attached files are read before sending any request, so a missing file does not leave a partially published release.
+  let mut assets = vec![];
+  for path in &options.assets {
+    let contents = std::fs::read(path).map_err(|e| error_read_file(path, e))?;
+    assets.push((file_name(path)?, contents));
+  }
+  let client = Client::new(&repository, options);
+  let prerelease = is_marked_prerelease(repository.forge(), &options.tag);
+  let (created, release) = match client.find_release(&options.tag)? {
+    Some(release) => (false, client.update_release(&release, &options.tag, &options.notes, prerelease)?),
+    None => (true, client.create_release(&options.tag, &options.notes, prerelease)?),
+  };
+  for (name, contents) in &assets {
+    if let Some(asset) = release.assets.iter().find(|asset| asset.name == *name) {
+      client.delete_asset(&release, asset)?;
+    }
+    client.upload_asset(&release, name, contents)?;
+  }
+  Ok(Release {
+    tag: options.tag.clone(),
+    url: release.url,
+    created,
+    prerelease,
+    assets: assets.into_iter().map(|(name, _)| name).collect(),
+  })
+}
+
+/// Returns `true` when the tag denotes a semver pre-release version, like `v1.2.0-rc.1`.
+pub fn is_prerelease(tag: &str) -> bool {
+  // Tags may have a prefix, like `v1.2.0` or `magg-v1.2.0`, the version starts with the first digit.
+  let Some(start) = tag.find(|ch: char| ch.is_ascii_digit()) else {
+    return false;
+  };
+  let version = tag[start..].split('+').next().unwrap_or_default();
+  let Some((core, pre_release)) = version.split_once('-') else {
+    return false;
+  };
+  let numbers = core.split('.').collect::<Vec<&str>>();
+  numbers.len() == 3 && numbers.iter().all(|number| !number.is_empty() && number.chars().all(|ch| ch.is_ascii_digit())) && !pre_release.is_empty()
+}
+
+/// Returns `true` when the release of the tag is marked as a prerelease on the forge, GitLab has no prerelease flag.
+pub fn is_marked_prerelease(forge: Forge, tag: &str) -> bool {
+  forge != Forge::GitLab && is_prerelease(tag)
+}
+
+/// Returns the name of the attached file.
+fn file_name(path: &Path) -> Result<String> {
+  path
+    .file_name()
+    .map(|name| name.to_string_lossy().to_string())
+    .ok_or_else(|| MaggError::validation(format!("invalid file name: {}", path.display())))
+}
+
+/// Release existing on the forge.
+struct ForgeRelease {
+  /// Identifier of the release, the tag is used by GitLab.
+  id: String,
+  /// URL of the release page.
+  url: String,
+  /// URL for uploading attached files, provided by GitHub.
+  upload_url: Option<String>,
+  /// Files attached to the release.
+  assets: Vec<ForgeAsset>,
+}
+
+/// File attached to the release.
+#[derive(Clone)]
+struct ForgeAsset {
+  /// Identifier of the attached file.
+  id: String,
+  /// Name of the attached file.
+  name: String,
+}
+
+/// Client of the forge REST API.
+struct Client {
+  /// Forge hosting the repository.
+  forge: Forge,
+  /// URL of the repository.
+  repository_url: String,
+  /// Base URL of the API endpoints of the repository.
+  base_url: String,
+  /// Authentication token.
+  token: Option<String>,
+  /// HTTP agent sending requests.
+  agent: ureq::Agent,
+}
+
+impl Client {
+  /// Creates the client of the forge hosting the repository.
+  fn new(repository: &Repository, options: &Options) -> Self {
+    let forge = repository.forge();
+    let api_url = options.api_url.clone().unwrap_or_else(|| repository.api_url());
+    let api_url = api_url.trim_end_matches('/');
+    let base_url = match forge {
+      Forge::GitHub | Forge::Gitea => format!("{api_url}/repos/{}", repository.path()),
+      Forge::GitLab => format!("{api_url}/projects/{}", encode(repository.path())),
+    };
+    Self {
+      forge,
+      repository_url: repository.to_string(),
+      base_url,
+      token: options.token.clone().or_else(|| default_token(forge)),
+      agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+    }
+  }
+
+  /// Returns the release of the tag, when it exists.
+  fn find_release(&self, tag: &str) -> Result<Option<ForgeRelease>> {
+    let url = match self.forge {
+      Forge::GitHub | Forge::Gitea => format!("{}/releases/tags/{}", self.base_url, encode(tag)),
+      Forge::GitLab => format!("{}/releases/{}", self.base_url, encode(tag)),
+    };
+    match self.call("GET", &url, None)? {
+      (404, _) => Ok(None),
+      (status, body) => self.parse_release(check("GET", &url, status, body)?).map(Some),
+    }
+  }
+
+  /// Creates the release of the tag.
+  fn create_release(&self, tag: &str, notes: &str, prerelease: bool) -> Result<ForgeRelease> {
+    let url = format!("{}/releases", self.base_url);
+    let release = self.send_json("POST", &url, &self.release_json(tag, notes, prerelease))?;
+    self.parse_release(release)
+  }
+
+  /// Updates notes and the prerelease flag of the existing release.
+  fn update_release(&self, release: &ForgeRelease, tag: &str, notes: &str, prerelease: bool) -> Result<ForgeRelease> {
+    let (method, url) = match self.forge {
+      Forge::GitHub | Forge::Gitea => ("PATCH", format!("{}/releases/{}", self.base_url, release.id)),
+      Forge::GitLab => ("PUT", format!("{}/releases/{}", self.base_url, encode(&release.id))),
+    };
+    let updated = self.parse_release(self.send_json(method, &url, &self.release_json(tag, notes, prerelease))?)?;
+    // Attached files are not always included in the response to the update, the ones listed before are kept.
+    Ok(ForgeRelease {
+      assets: if updated.assets.is_empty() { release.assets.clone() } else { updated.assets },
+      ..updated
+    })
+  }
+
+  /// Deletes the file attached to the release.
+  fn delete_asset(&self, release: &ForgeRelease, asset: &ForgeAsset) -> Result<()> {
+    let url = match self.forge {
+      Forge::GitHub => format!("{}/releases/assets/{}", self.base_url, asset.id),
+      Forge::Gitea => format!("{}/releases/{}/assets/{}", self.base_url, release.id, asset.id),
+      Forge::GitLab => format!("{}/releases/{}/assets/links/{}", self.base_url, encode(&release.id), asset.id),
+    };
+    let (status, body) = self.call("DELETE", &url, None)?;
+    check("DELETE", &url, status, body).map(|_| ())
+  }
+
+  /// Attaches the file to the release.
+  fn upload_asset(&self, release: &ForgeRelease, name: &str, contents: &[u8]) -> Result<()> {
+    match self.forge {
+      Forge::GitHub => {
+        // The upload URL is a URI template, like `https://uploads.github.com/repos/o/r/releases/1/assets{?name,label}`.
+        let upload_url = match &release.upload_url {
+          Some(upload_url) => upload_url.split('{').next().unwrap_or_default().to_string(),
+          None => format!("{}/releases/{}/assets", self.base_url, release.id),
+        };
+        let url = format!("{upload_url}?name={}", encode(name));
+        self.send("POST", &url, Some(("application/octet-stream".to_string(), contents.to_vec())))?;
+      }
+      Forge::Gitea => {
+        let url = format!("{}/releases/{}/assets?name={}", self.base_url, release.id, encode(name));
+        self.send("POST", &url, Some(multipart("attachment", name, contents)))?;
+      }
+      Forge::GitLab => {
+        // Files are uploaded to the project first and then linked to the release.
+        let upload = self.send("POST", &format!("{}/uploads", self.base_url), Some(multipart("file", name, contents)))?;
+        let path = upload["url"].as_str().ok_or_else(|| invalid_response("uploaded file", &upload))?;
+        let link = json!({ "name": name, "url": format!("{}{path}", self.repository_url), "link_type": "package" });
+        self.send_json("POST", &format!("{}/releases/{}/assets/links", self.base_url, encode(&release.id)), &link)?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns the JSON body of requests creating or updating the release.
+  fn release_json(&self, tag: &str, notes: &str, prerelease: bool) -> Value {
+    match self.forge {
+      Forge::GitHub | Forge::Gitea => json!({ "tag_name": tag, "name": tag, "body": notes, "prerelease": prerelease }),
+      // GitLab has no prerelease flag.
+      Forge::GitLab => json!({ "tag_name": tag, "name": tag, "description": notes }),
+    }
+  }
+
+  /// Parses the release returned by the forge.
+  fn parse_release(&self, release: Value) -> Result<ForgeRelease> {
+    let string = |value: &Value| match value {
+      Value::String(value) => Some(value.clone()),
+      Value::Number(value) => Some(value.to_string()),
+      _ => None,
+    };
+    let (id, url, assets) = match self.forge {
+      Forge::GitHub | Forge::Gitea => (string(&release["id"]), string(&release["html_url"]), &release["assets"]),
+      Forge::GitLab => (string(&release["tag_name"]), string(&release["_links"]["self"]), &release["assets"]["links"]),
+    };
+    let (Some(id), Some(url)) = (id, url) else {
+      return Err(invalid_response("release", &release));
+    };
+    let assets = assets
+      .as_array()
+      .map(|assets| assets.as_slice())
+      .unwrap_or_default()
+      .iter()
+      .map(|asset| match (string(&asset["id"]), string(&asset["name"])) {
+        (Some(id), Some(name)) => Ok(ForgeAsset { id, name }),
+        _ => Err(invalid_response("release asset", asset)),
+      })
+      .collect::<Result<Vec<ForgeAsset>>>()?;
+    Ok(ForgeRelease {
+      id,
+      url,
+      upload_url: release["upload_url"].as_str().map(|upload_url| upload_url.to_string()),
+      assets,
+    })
+  }
+
+  /// Sends the request with the JSON body and returns the JSON response.
+  fn send_json(&self, method: &str, url: &str, body: &Value) -> Result<Value> {
+    self.send(method, url, Some(("application/json".to_string(), body.to_string().into_bytes())))
+  }
+
+  /// Sends the request and returns the JSON response, an empty response is returned as `null`.
+  fn send(&self, method: &str, url: &str, content: Option<(String, Vec<u8>)>) -> Result<Value> {
+    let (status, body) = self.call(method, url, content)?;
+    check(method, url, status, body)
+  }
+
+  /// Sends the request and returns the status and the body of the response.
+  fn call(&self, method: &str, url: &str, content: Option<(String, Vec<u8>)>) -> Result<(u16, String)> {
+    let mut request = self.agent.request(method, url);
+    request = match self.forge {
+      Forge::GitHub => request.set("Accept", "application/vnd.github+json"),
+      Forge::GitLab | Forge::Gitea => request.set("Accept", "application/json"),
+    };
+    if let Some(token) = &self.token {
+      request = match self.forge {
+        Forge::GitHub => request.set("Authorization", &format!("Bearer {token}")),
+        Forge::GitLab => request.set("PRIVATE-TOKEN", token),
+        Forge::Gitea => request.set("Authorization", &format!("token {token}")),
+      };
+    }
+    let response = match content {
+      Some((content_type, bytes)) => request.set("Content-Type", &content_type).send_bytes(&bytes),
+      None => request.call(),
+    };
+    let response = match response {
+      Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+      Err(e) => return Err(MaggError::forge(format!("forge request failed: {method} {url}")).with_source(e)),
+    };
+    let status = response.status();
+    let body = response
+      .into_string()
+      .map_err(|e| MaggError::forge(format!("failed to read forge response: {method} {url}")).with_source(e))?;
+    Ok((status, body))
+  }
+}
+
+/// Checks the status of the response and returns the parsed JSON body.
+fn check(method: &str, url: &str, status: u16, body: String) -> Result<Value> {
+  if !(200..300).contains(&status) {
+    return Err(MaggError::forge(format!("forge request failed: {method} {url}, status {status}: {}", body.trim())));
+  }
+  if body.trim().is_empty() {
+    return Ok(Value::Null);
+  }
+  serde_json::from_str(&body).map_err(|e| MaggError::parse(format!("failed to parse forge response: {method} {url}")).with_source(e))
+}
+
+/// Creates the error of the response with unexpected structure.
+fn invalid_response(kind: &str, value: &Value) -> MaggError {
+  MaggError::parse(format!("invalid {kind} in forge response: {value}"))
+}
+
+/// Returns the authentication token of the forge taken from the environment.
+fn default_token(forge: Forge) -> Option<String> {
+  let var = |name: &str| std::env::var(name).ok().map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+  match forge {
+    Forge::GitHub => var("GITHUB_TOKEN").or_else(|| var("GH_TOKEN")).or_else(|| {
+      let output = std::process::Command::new("gh").args(["auth", "token"]).output().ok()?;
+      let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+      (output.status.success() && !token.is_empty()).then_some(token)
+    }),
+    Forge::GitLab => var("GITLAB_TOKEN"),
+    Forge::Gitea => var("GITEA_TOKEN"),
+  }
+}
+
+/// Percent-encodes the text used as a path segment or a query value.
+fn encode(text: &str) -> String {
+  text
+    .bytes()
+    .map(|byte| match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+      _ => format!("%{byte:02X}"),
+    })
+    .collect()
+}
+
+/// Returns the content type and the body of the multipart request with a single file.
+fn multipart(field: &str, file_name: &str, contents: &[u8]) -> (String, Vec<u8>) {
+  let file_name = file_name.replace('"', "%22");
+  let mut body =
+    format!("--{MULTIPART_BOUNDARY}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"{file_name}\"\r\nContent-Type: application/octet-stream\r\n\r\n").into_bytes();
+  body.extend_from_slice(contents);
+  body.extend_from_slice(format!("\r\n--{MULTIPART_BOUNDARY}--\r\n").as_bytes());
+  (format!("multipart/form-data; boundary={MULTIPART_BOUNDARY}"), body)
+}
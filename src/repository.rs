@@ -0,0 +1,161 @@
+//! # Repository URL
+//!
+//! Normalizes repository URLs given in HTTPS, SSH or scp-like form, with or without
+//! the `.git` suffix, and derives forge-specific URLs of badges and files from them.
+
+use crate::errors::*;
+use crate::manifest::Manifest;
+use crate::utils;
+use std::fmt::Display;
+use std::path::Path;
+
+/// Name of the default branch used in links to files.
+pub const DEFAULT_BRANCH: &str = "main";
+
+/// Forge hosting the repository.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Forge {
+  /// GitHub or GitHub Enterprise, also assumed for unknown hosts.
+  GitHub,
+  /// GitLab.com or self-hosted GitLab.
+  GitLab,
+  /// Gitea, Forgejo or Codeberg.
+  Gitea,
+}
+
+/// Normalized repository URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repository {
+  /// Forge hosting the repository.
+  forge: Forge,
+  /// Host name, without credentials and port.
+  host: String,
+  /// Repository path, like `EngosSoftware/magg`.
+  path: String,
+}
+
+impl Display for Repository {
+  /// Implementation of [Display] trait for [Repository].
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "https://{}/{}", self.host, self.path)
+  }
+}
+
+impl Repository {
+  /// Parses the repository URL in any of the supported forms:
+  ///
+  /// - `https://github.com/EngosSoftware/magg.git`,
+  /// - `https://github.com/EngosSoftware/magg`,
+  /// - `ssh://git@github.com/EngosSoftware/magg.git`,
+  /// - `git@github.com:EngosSoftware/magg.git`.
+  pub fn parse(url: &str) -> Result<Self> {
+    let url = url.trim();
+    let invalid = || MaggError::validation(format!("invalid repository URL: {url}"));
+    let (authority, path) = if let Some((scheme, rest)) = url.split_once("://") {
+      if !matches!(scheme, "https" | "http" | "ssh" | "git" | "git+https" | "git+ssh") {
+        return Err(invalid());
+      }
+      rest.split_once('/').ok_or_else(invalid)?
+    } else {
+      // The scp-like syntax, like `git@github.com:EngosSoftware/magg.git`.
+      url.split_once(':').ok_or_else(invalid)?
+    };
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default().to_lowercase();
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path).trim_end_matches('/');
+    if host.is_empty() || !path.contains('/') || path.contains(' ') {
+      return Err(invalid());
+    }
+    Ok(Self {
+      forge: Forge::from_host(&host),
+      host,
+      path: path.to_string(),
+    })
+  }
+
+  /// Resolves the repository URL of the package.
+  ///
+  /// The repository URL is taken from `package.repository` (possibly inherited from the workspace),
+  /// when the repository URL is not specified, the URL of the `origin` Git remote is used.
+  pub fn resolve(manifest: &Manifest) -> Result<Self> {
+    match manifest.package_str("repository")? {
+      Some(url) => Self::parse(url),
+      None => Self::from_git_remote(manifest.dir()),
+    }
+  }
+
+  /// Returns the repository URL of the `origin` Git remote.
+  pub fn from_git_remote(dir: impl AsRef<Path>) -> Result<Self> {
+    let url = utils::git(dir, &["remote", "get-url", "origin"]).map_err(|_| MaggError::manifest("repository URL not found in Cargo.toml nor in Git remote 'origin'"))?;
+    Self::parse(&url)
+  }
+
+  /// Returns the repository path, like `EngosSoftware/magg`.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  /// Returns the forge hosting the repository.
+  pub fn forge(&self) -> Forge {
+    self.forge
+  }
+
+  /// Returns the base URL of the REST API of the forge.
+  pub fn api_url(&self) -> String {
+    match self.forge {
+      Forge::GitHub if self.host == "github.com" => "https://api.github.com".to_string(),
+      Forge::GitHub => format!("https://{}/api/v3", self.host),
+      Forge::GitLab => format!("https://{}/api/v4", self.host),
+      Forge::Gitea => format!("https://{}/api/v1", self.host),
+    }
+  }
+
+  /// Returns the URL of the file in the default branch.
+  pub fn blob_url(&self, file_name: &str) -> String {
+    match self.forge {
+      Forge::GitHub => format!("{self}/blob/{DEFAULT_BRANCH}/{file_name}"),
+      Forge::GitLab => format!("{self}/-/blob/{DEFAULT_BRANCH}/{file_name}"),
+      Forge::Gitea => format!("{self}/src/branch/{DEFAULT_BRANCH}/{file_name}"),
+    }
+  }
+
+  /// Returns the URL of the commit with the specified hash.
+  pub fn commit_url(&self, hash: &str) -> String {
+    match self.forge {
+      Forge::GitHub | Forge::Gitea => format!("{self}/commit/{hash}"),
+      Forge::GitLab => format!("{self}/-/commit/{hash}"),
+    }
+  }
+
+  /// Returns the URL of the issue tracker.
+  pub fn issues_url(&self) -> String {
+    match self.forge {
+      Forge::GitHub | Forge::Gitea => format!("{self}/issues"),
+      Forge::GitLab => format!("{self}/-/issues"),
+    }
+  }
+
+  /// Returns the URL of the status badge of the workflow defined in the file with specified name.
+  ///
+  /// GitLab has no per-workflow badges, the pipeline status badge of the default branch is returned instead.
+  pub fn workflow_badge_url(&self, workflow: &str) -> String {
+    match self.forge {
+      Forge::GitHub | Forge::Gitea => format!("{self}/actions/workflows/{workflow}/badge.svg"),
+      Forge::GitLab => format!("{self}/badges/{DEFAULT_BRANCH}/pipeline.svg"),
+    }
+  }
+}
+
+impl Forge {
+  /// Recognizes the forge by the host name.
+  fn from_host(host: &str) -> Self {
+    if host.contains("gitlab") {
+      Self::GitLab
+    } else if host == "codeberg.org" || host.contains("gitea") || host.contains("forgejo") {
+      Self::Gitea
+    } else {
+      Self::GitHub
+    }
+  }
+}
@@ -0,0 +1,175 @@
+//! # Crate repository scaffolding
+//!
+//! Creates a new crate with the complete repository layout: `Cargo.toml`, sources, `Taskfile.yml`,
+//! `rustfmt.toml`, `docs/README.md`, the `manual/` mdBook skeleton, and for binaries the `tests/` layout
+//! using `cli-assert`. Then README.md, license files and the code of conduct are generated
+//! like by the `readme`, `licenses` and `code-of-conduct` commands.
+
+use crate::errors::*;
+use crate::manifest::{MANIFEST_FILE_NAME, Manifest};
+use crate::repository::Repository;
+use crate::utils;
+use crate::writer::RECORD_FILE_NAME;
+use crate::{code_of_conduct, licenses, readme};
+use regex::Regex;
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Default license of the scaffolded crate.
+pub const DEFAULT_LICENSE: &str = "MIT OR Apache-2.0";
+
+/// Name of the README body file.
+const README_BODY: &str = "docs/README.md";
+
+/// Pattern for matching valid crate names.
+const CRATE_NAME_PATTERN: &str = r#"^[a-zA-Z][a-zA-Z0-9_-]*$"#;
+
+/// Regular expression for matching valid crate names.
+static RE_CRATE_NAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(CRATE_NAME_PATTERN).unwrap());
+
+/// Templates of files common for libraries and binaries.
+const TEMPLATES: [(&str, &str); 7] = [
+  (".gitignore", include_str!("GITIGNORE")),
+  ("rustfmt.toml", include_str!("RUSTFMT")),
+  ("Taskfile.yml", include_str!("TASKFILE")),
+  (README_BODY, include_str!("DOCS_README")),
+  ("manual/book.toml", include_str!("BOOK_TOML")),
+  ("manual/src/SUMMARY.md", include_str!("SUMMARY")),
+  ("manual/src/README.md", include_str!("MANUAL_README")),
+];
+
+/// Templates of files specific for libraries.
+const LIBRARY_TEMPLATES: [(&str, &str); 1] = [("src/lib.rs", include_str!("LIB_RS"))];
+
+/// Templates of files specific for binaries.
+const BINARY_TEMPLATES: [(&str, &str); 4] = [
+  ("src/main.rs", include_str!("MAIN_RS")),
+  ("tests/mod.rs", include_str!("TESTS_MOD")),
+  ("tests/test_cli/mod.rs", include_str!("TESTS_CLI_MOD")),
+  ("tests/test_cli/test_main.rs", include_str!("TESTS_CLI_MAIN")),
+];
+
+/// Tasks removed from `Taskfile.yml` of libraries.
+const BINARY_TASKS: [&str; 2] = ["  install:", "  uninstall:"];
+
+/// File names with contents of scaffolded files.
+pub type Files = Vec<(String, String)>;
+
+/// Properties of the scaffolded crate.
+pub struct Options {
+  /// Name of the crate.
+  pub name: String,
+  /// Authors, like `Name <e-mail>`.
+  pub authors: Vec<String>,
+  /// Description of the crate.
+  pub description: String,
+  /// SPDX license expression.
+  pub license: String,
+  /// Repository URL.
+  pub repository: String,
+  /// Flag indicating if the crate is a library, otherwise a binary.
+  pub library: bool,
+}
+
+/// Scaffolds the crate in the specified directory, no files are written.
+/// The directory must not contain the package manifest, existing files are used instead of templates
+/// when generating other files. Returns the files scaffolded from templates, followed by the generated files,
+/// with paths relative to the directory.
+pub fn scaffold(dir: &Path, options: &Options) -> Result<(Files, Files)> {
+  validate(options)?;
+  if dir.join(MANIFEST_FILE_NAME).exists() {
+    return Err(MaggError::validation(format!("{} already exists", dir.join(MANIFEST_FILE_NAME).display())));
+  }
+  let mut files = vec![(MANIFEST_FILE_NAME.to_string(), manifest(options))];
+  let specific: &[(&str, &str)] = if options.library { &LIBRARY_TEMPLATES } else { &BINARY_TEMPLATES };
+  for (file_name, template) in TEMPLATES.iter().chain(specific) {
+    let mut content = substitute(template, options);
+    if *file_name == "Taskfile.yml" && options.library {
+      content = content
+        .split("\n\n")
+        .filter(|task| !BINARY_TASKS.iter().any(|name| task.starts_with(name)))
+        .collect::<Vec<&str>>()
+        .join("\n\n");
+    }
+    files.push((file_name.to_string(), content));
+  }
+  // Generate files like the dedicated commands do, in a staging directory, so nothing is written before the writer decides.
+  let staging_dir = tempfile::TempDir::new().map_err(|e| MaggError::io("failed to create staging directory", e))?;
+  for (file_name, content) in &files {
+    let existing = dir.join(file_name);
+    let content = if existing.exists() { utils::read_file(&existing)? } else { content.clone() };
+    utils::write_file(staging_dir.path().join(file_name), &content)?;
+  }
+  let manifest = Manifest::load(staging_dir.path())?;
+  let mut generated = readme::scaffold_readmes(&manifest, &readme::Options::new().body(README_BODY))?;
+  generated.extend(licenses::get_license_files(&manifest)?);
+  generated.push(("CODE_OF_CONDUCT.md".to_string(), code_of_conduct::get_code_of_conduct(&manifest)?));
+  Ok((files, generated))
+}
+
+/// Returns the default author, `Name <e-mail>` taken from Git configuration.
+pub fn default_author() -> Option<String> {
+  let config = |key: &str| {
+    utils::git("", &["config", key])
+      .ok()
+      .map(|value| value.trim().to_string())
+      .filter(|value| !value.is_empty())
+  };
+  Some(format!("{} <{}>", config("user.name")?, config("user.email")?))
+}
+
+/// Validates properties of the scaffolded crate.
+fn validate(options: &Options) -> Result<()> {
+  if !RE_CRATE_NAME.is_match(&options.name) {
+    return Err(MaggError::validation(format!("invalid crate name: {}", options.name)));
+  }
+  if !options.authors.iter().any(|author| author.contains('<')) {
+    return Err(MaggError::validation("e-mail address of an author is required, like: --author 'Name <e-mail>'"));
+  }
+  Repository::parse(&options.repository)?;
+  Ok(())
+}
+
+/// Returns the value quoted as TOML string.
+fn quoted(value: &str) -> String {
+  toml::Value::String(value.to_string()).to_string()
+}
+
+/// Returns the content of the package manifest.
+fn manifest(options: &Options) -> String {
+  let authors = options.authors.iter().map(|author| quoted(author)).collect::<Vec<String>>().join(", ");
+  let mut output = String::new();
+  _ = writeln!(&mut output, "[package]");
+  _ = writeln!(&mut output, "name = {}", quoted(&options.name));
+  _ = writeln!(&mut output, "version = \"0.1.0\"");
+  _ = writeln!(&mut output, "edition = \"2024\"");
+  _ = writeln!(&mut output, "authors = [{authors}]");
+  if !options.description.is_empty() {
+    _ = writeln!(&mut output, "description = {}", quoted(&options.description));
+  }
+  _ = writeln!(&mut output, "license = {}", quoted(&options.license));
+  _ = writeln!(&mut output, "repository = {}", quoted(&options.repository));
+  _ = writeln!(&mut output, "readme = \"README.md\"");
+  _ = writeln!(&mut output, "keywords = []");
+  _ = writeln!(&mut output, "categories = []");
+  _ = writeln!(&mut output, "exclude = [{}]", quoted(RECORD_FILE_NAME));
+  _ = writeln!(&mut output);
+  _ = writeln!(&mut output, "[dependencies]");
+  if !options.library {
+    _ = writeln!(&mut output);
+    _ = writeln!(&mut output, "[dev-dependencies]");
+    _ = writeln!(&mut output, "cli-assert = \"0.1\"");
+  }
+  output
+}
+
+/// Replaces placeholders in the template.
+fn substitute(template: &str, options: &Options) -> String {
+  let authors = options.authors.iter().map(|author| quoted(author)).collect::<Vec<String>>().join(", ");
+  template
+    .replace("::NAME::", &options.name)
+    .replace("::AUTHORS::", &authors)
+    .replace("::QUOTED_DESCRIPTION::", &quoted(&options.description))
+    .replace("::DESCRIPTION::", &options.description)
+}
@@ -1,6 +1,7 @@
 use crate::errors::*;
-use std::io::{self, Write};
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Separator line.
 pub const SEPARATOR_LINE: &str = "────────────────────────────────────────────────────────────────────────────────";
@@ -10,23 +11,122 @@ pub fn read_file(file_name: impl AsRef<Path>) -> Result<String> {
   std::fs::read_to_string(path).map_err(|e| error_read_file(path, e))
 }
 
+/// Writes the file atomically, the contents is written to a temporary file first, which then replaces the target file.
 pub fn write_file(file_name: impl AsRef<Path>, contents: &str) -> Result<()> {
   let file_path = file_name.as_ref();
-  std::fs::write(file_path, contents).map_err(|e| MaggError::new(format!("failed to write file {}, reason: {}", file_path.display(), e)))
+  if let Some(dir) = file_path.parent().filter(|dir| !dir.as_os_str().is_empty() && !dir.exists()) {
+    std::fs::create_dir_all(dir).map_err(|e| MaggError::io(format!("failed to create directory {}", dir.display()), e))?;
+  }
+  let failed = |e: io::Error| MaggError::io(format!("failed to write file {}", file_path.display()), e);
+  let file_name = file_path
+    .file_name()
+    .ok_or_else(|| MaggError::validation(format!("invalid file name: {}", file_path.display())))?;
+  let temp_path = file_path.with_file_name(format!(".{}.{}.tmp", file_name.to_string_lossy(), std::process::id()));
+  std::fs::write(&temp_path, contents).map_err(failed)?;
+  std::fs::rename(&temp_path, file_path).map_err(|e| {
+    _ = std::fs::remove_file(&temp_path);
+    failed(e)
+  })
 }
 
 pub fn parse_toml(file_name: impl AsRef<Path>) -> Result<toml::Value> {
-  toml::from_str(&read_file(file_name)?).map_err(|e| MaggError::new(e.to_string()))
+  let path = file_name.as_ref();
+  toml::from_str(&read_file(path)?).map_err(|e| MaggError::parse(format!("failed to parse file {}", path.display())).with_source(e))
+}
+
+/// Returns the absolute path of the directory, an empty path denotes the current directory.
+pub fn absolute(dir: impl AsRef<Path>) -> Result<PathBuf> {
+  let dir = dir.as_ref();
+  let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+  std::path::absolute(dir).map_err(|e| MaggError::io(format!("failed to resolve directory {}", dir.display()), e))
+}
+
+/// Returns the cache directory of the user, following conventions of the platform.
+pub fn cache_dir() -> Option<PathBuf> {
+  let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+  if cfg!(windows) {
+    var("LOCALAPPDATA")
+  } else if cfg!(target_os = "macos") {
+    var("HOME").map(|home| home.join("Library").join("Caches"))
+  } else {
+    var("XDG_CACHE_HOME").or_else(|| var("HOME").map(|home| home.join(".cache")))
+  }
 }
 
-pub fn get_package_name(parsed: &toml::Value) -> &str {
-  parsed["package"]["name"].as_str().expect("package.name not found in Cargo.toml")
+/// Applies the function to all items using at most `jobs` threads, results are returned in the order of items.
+pub fn parallel_map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+  let next = AtomicUsize::new(0);
+  let mut results = std::thread::scope(|scope| {
+    let workers = (0..jobs.clamp(1, items.len().max(1)))
+      .map(|_| {
+        scope.spawn(|| {
+          let mut results = vec![];
+          loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            if index >= items.len() {
+              break results;
+            }
+            results.push((index, f(&items[index])));
+          }
+        })
+      })
+      .collect::<Vec<_>>();
+    workers
+      .into_iter()
+      .flat_map(|worker| worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+      .collect::<Vec<(usize, R)>>()
+  });
+  results.sort_by_key(|(index, _)| *index);
+  results.into_iter().map(|(_, result)| result).collect()
 }
-pub fn get_repository(parsed: &toml::Value) -> &str {
-  parsed["package"]["repository"].as_str().expect("package.repository not found in Cargo.toml")
+
+/// Executes Git command in the specified directory and returns its standard output.
+pub fn git(dir: impl AsRef<Path>, args: &[&str]) -> Result<String> {
+  let output = std::process::Command::new("git")
+    .args(args)
+    .current_dir(absolute(dir)?)
+    .output()
+    .map_err(|e| error_spawn_command("git", e))?;
+  let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+  if output.status.success() {
+    Ok(stdout)
+  } else {
+    Err(error_execute_command("git", output.status, stdout, String::from_utf8_lossy(&output.stderr)))
+  }
+}
+
+/// Status of the generated file on disk.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FileStatus {
+  /// The file does not exist.
+  Missing,
+  /// The content of the file differs from the generated one.
+  Modified,
+}
+
+/// Compares generated files with files in the specified directory.
+/// Returns the names and statuses of files that are missing or modified.
+pub fn check_files(dir: impl AsRef<Path>, files: &[(String, String)]) -> Result<Vec<(String, FileStatus)>> {
+  let mut statuses = vec![];
+  for (file_name, content) in files {
+    let path = dir.as_ref().join(file_name);
+    if !path.exists() {
+      statuses.push((file_name.clone(), FileStatus::Missing));
+    } else if read_file(&path)? != *content {
+      statuses.push((file_name.clone(), FileStatus::Modified));
+    }
+  }
+  Ok(statuses)
 }
 
-pub fn step_progress() {
-  print!("·");
-  io::stdout().flush().unwrap();
+/// Formats the contact as Markdown, e-mail addresses and URLs become links, any other text is returned unchanged.
+pub fn markdown_contact(contact: &str) -> String {
+  if contact.starts_with("https://") || contact.starts_with("http://") {
+    format!("[{contact}]({contact})")
+  } else if !contact.contains(char::is_whitespace) && contact.contains('@') {
+    let email = contact.strip_prefix("mailto:").unwrap_or(contact);
+    format!("[{email}](mailto:{email})")
+  } else {
+    contact.to_string()
+  }
 }
@@ -0,0 +1,307 @@
+//! # Safe file writer
+//!
+//! Generated files are written through the [Writer], which never overwrites files
+//! edited since they were last generated unless forced, previews changes as coloured diffs
+//! in dry-run mode, and collects the summary of created, updated and unchanged files.
+//!
+//! Hashes of the last generated contents are recorded in a single [RECORD_FILE_NAME] file
+//! in the record directory, keyed by paths relative to it. The record is meant to be committed
+//! along with the generated files and excluded from the published package. Existing files
+//! without a recorded hash are overwritten and recorded, so generated files become protected
+//! on their first write. Lines updated by other tools after generation, like the coverage
+//! badge updated by coverio, are excluded from hashes.
+
+use crate::errors::*;
+use crate::utils::{absolute, read_file, write_file};
+use antex::{StyledText, Text, auto};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Number of unchanged lines displayed around changed lines in diffs.
+const CONTEXT_LINES: usize = 3;
+
+/// Name of the file recording hashes of the last generated contents of files.
+pub const RECORD_FILE_NAME: &str = ".magg-generated";
+
+/// Prefixes of lines updated by other tools after generation, excluded from hashes.
+const UNTRACKED_LINE_PREFIXES: [&str; 1] = ["[cov-badge]: "];
+
+/// Change of the written file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Change {
+  /// The file did not exist and was created.
+  Created,
+  /// The file existed with different content and was overwritten.
+  Updated,
+  /// The file already had the same content.
+  Unchanged,
+  /// The file was edited since it was last generated and was not overwritten, because overwriting was not forced.
+  Refused,
+}
+
+/// Kind of the written file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Kind {
+  /// File regenerated from the manifest, protected against overwriting edits made since it was last generated.
+  Generated,
+  /// File scaffolded from a template, protected against overwriting existing files.
+  Template,
+  /// Existing file edited by hand and updated in place.
+  Source,
+}
+
+/// Writer of generated files.
+pub struct Writer {
+  /// Flag indicating if files edited since they were last generated may be overwritten.
+  force: bool,
+  /// Flag indicating if changes are only previewed, without writing any files.
+  dry_run: bool,
+  /// Directory containing the record of generated files.
+  record_dir: PathBuf,
+  /// Paths of files passed to the writer and their changes.
+  changes: Vec<(PathBuf, Change)>,
+  /// Paths of existing files overwritten without a recorded hash.
+  adopted: Vec<PathBuf>,
+}
+
+impl Writer {
+  /// Creates a new writer recording hashes of generated files in the specified directory.
+  pub fn new(force: bool, dry_run: bool, record_dir: impl AsRef<Path>) -> Self {
+    Self {
+      force,
+      dry_run,
+      record_dir: record_dir.as_ref().to_path_buf(),
+      changes: vec![],
+      adopted: vec![],
+    }
+  }
+
+  /// Writes the generated file, unless it has the same content or it was edited since it was last generated and overwriting is not forced.
+  /// Existing files without a recorded hash are overwritten.
+  /// Returns the change and, in dry-run mode, the diff between the current and the new content.
+  pub fn write(&mut self, path: impl AsRef<Path>, contents: &str) -> Result<(Change, Option<Text>)> {
+    self.apply(path.as_ref(), contents, Kind::Generated)
+  }
+
+  /// Writes the file scaffolded from a template, unless it has the same content or it exists and overwriting is not forced.
+  /// Scaffolded files are edited by hand afterwards, so their hashes are not recorded.
+  pub fn write_template(&mut self, path: impl AsRef<Path>, contents: &str) -> Result<(Change, Option<Text>)> {
+    self.apply(path.as_ref(), contents, Kind::Template)
+  }
+
+  /// Updates the existing file edited by hand, like a source file receiving a license header.
+  /// The file is always overwritten, unless in dry-run mode, and its hash is not recorded.
+  pub fn update(&mut self, path: impl AsRef<Path>, contents: &str) -> Result<(Change, Option<Text>)> {
+    self.apply(path.as_ref(), contents, Kind::Source)
+  }
+
+  /// Applies the new content of the file of the specified kind.
+  fn apply(&mut self, path: &Path, contents: &str, kind: Kind) -> Result<(Change, Option<Text>)> {
+    let current = if path.exists() { Some(read_file(path)?) } else { None };
+    let record_path = self.record_dir.join(RECORD_FILE_NAME);
+    let key = record_key(&self.record_dir, path)?;
+    let mut record = if kind == Kind::Generated && record_path.exists() {
+      parse_record(&read_file(&record_path)?)
+    } else {
+      vec![]
+    };
+    let recorded_hash = record.iter().find(|(name, _)| *name == key).map(|(_, hash)| hash.clone());
+    let change = match (&current, &recorded_hash) {
+      (None, _) => Change::Created,
+      (Some(current), _) if current == contents => Change::Unchanged,
+      (Some(_), _) if self.force || kind == Kind::Source => Change::Updated,
+      (Some(_), None) if kind == Kind::Generated => {
+        self.adopted.push(path.to_path_buf());
+        Change::Updated
+      }
+      (Some(current), Some(recorded_hash)) if kind == Kind::Generated && *recorded_hash == hash(current) => Change::Updated,
+      (Some(_), _) => Change::Refused,
+    };
+    let diff = (self.dry_run && change != Change::Unchanged).then(|| diff(path, current.as_deref().unwrap_or_default(), contents));
+    if !self.dry_run && matches!(change, Change::Created | Change::Updated) {
+      write_file(path, contents)?;
+    }
+    // Unchanged files are recorded too, so files generated before the record existed become protected.
+    if kind == Kind::Generated && !self.dry_run && change != Change::Refused && recorded_hash != Some(hash(contents)) {
+      record.retain(|(name, _)| *name != key);
+      record.push((key, hash(contents)));
+      record.sort();
+      write_file(&record_path, &format_record(&record))?;
+    }
+    self.changes.push((path.to_path_buf(), change));
+    Ok((change, diff))
+  }
+
+  /// Returns `true` when changes are only previewed.
+  pub fn is_dry_run(&self) -> bool {
+    self.dry_run
+  }
+
+  /// Returns `true` when no files were passed to the writer.
+  pub fn is_empty(&self) -> bool {
+    self.changes.is_empty()
+  }
+
+  /// Returns paths of files that were not overwritten.
+  pub fn refused(&self) -> Vec<&Path> {
+    self
+      .changes
+      .iter()
+      .filter(|(_, change)| *change == Change::Refused)
+      .map(|(path, _)| path.as_path())
+      .collect()
+  }
+
+  /// Returns paths of existing files that were overwritten without a recorded hash.
+  pub fn adopted(&self) -> &[PathBuf] {
+    &self.adopted
+  }
+
+  /// Returns the summary listing changed files, followed by the number of files per change.
+  pub fn summary(&self) -> String {
+    let mut output = String::new();
+    let verb = |change: Change| match change {
+      Change::Created => "created",
+      Change::Updated => "updated",
+      Change::Unchanged => "unchanged",
+      Change::Refused => "not overwritten",
+    };
+    for (path, change) in &self.changes {
+      if *change != Change::Unchanged {
+        _ = writeln!(&mut output, "{}: {}", verb(*change), path.display());
+      }
+    }
+    let counts = [Change::Created, Change::Updated, Change::Unchanged, Change::Refused]
+      .into_iter()
+      .map(|change| (change, self.changes.iter().filter(|(_, file_change)| *file_change == change).count()))
+      .filter(|(change, count)| *count > 0 || *change != Change::Refused)
+      .map(|(change, count)| format!("{count} {}", verb(change)))
+      .collect::<Vec<String>>();
+    _ = writeln!(&mut output, "{}{}", counts.join(", "), if self.dry_run { " (dry run)" } else { "" });
+    output
+  }
+}
+
+/// Returns the hexadecimal FNV-1a hash of the contents, stable across platforms and versions.
+/// Untracked lines are excluded from the hash.
+fn hash(contents: &str) -> String {
+  let hash = contents
+    .split_inclusive('\n')
+    .filter(|line| !UNTRACKED_LINE_PREFIXES.iter().any(|prefix| line.starts_with(prefix)))
+    .flat_map(|line| line.bytes())
+    .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3));
+  format!("{hash:016x}")
+}
+
+/// Returns the key of the file in the record: the path relative to the record directory with `/` separators,
+/// or the absolute path for files outside the record directory.
+fn record_key(record_dir: &Path, path: &Path) -> Result<String> {
+  let path = absolute(path)?;
+  let relative = path.strip_prefix(absolute(record_dir)?).unwrap_or(&path);
+  Ok(relative.to_string_lossy().replace('\\', "/"))
+}
+
+/// Returns file names and hashes parsed from the record, lines have the format `<hash>  <file name>`.
+fn parse_record(record: &str) -> Vec<(String, String)> {
+  record
+    .lines()
+    .filter_map(|line| line.split_once("  "))
+    .map(|(hash, file_name)| (file_name.to_string(), hash.to_string()))
+    .collect()
+}
+
+/// Returns the record listing hashes of file names.
+fn format_record(record: &[(String, String)]) -> String {
+  let mut output = String::new();
+  for (file_name, hash) in record {
+    _ = writeln!(&mut output, "{hash}  {file_name}");
+  }
+  output
+}
+
+/// Returns the unified diff between the current and the new content of the file, coloured when printed to a terminal.
+pub fn diff(path: &Path, current: &str, new: &str) -> Text {
+  let current = current.lines().collect::<Vec<&str>>();
+  let new = new.lines().collect::<Vec<&str>>();
+  let edits = edits(&current, &new);
+  let mut text = auto().bold().s(format!("--- {}\n+++ {}\n", path.display(), path.display())).reset();
+  // Changed edits extended by the context form hunks, adjacent hunks are merged.
+  let changed = edits
+    .iter()
+    .enumerate()
+    .filter(|(_, edit)| !matches!(edit, Edit::Same(_)))
+    .map(|(index, _)| index)
+    .collect::<Vec<usize>>();
+  let mut hunks: Vec<(usize, usize)> = vec![];
+  for index in changed {
+    let start = index.saturating_sub(CONTEXT_LINES);
+    let end = (index + CONTEXT_LINES + 1).min(edits.len());
+    match hunks.last_mut() {
+      Some((_, last_end)) if start <= *last_end => *last_end = end,
+      _ => hunks.push((start, end)),
+    }
+  }
+  for (start, end) in hunks {
+    let hunk = &edits[start..end];
+    let (current_start, new_start) = edits[..start].iter().fold((1, 1), |(current, new), edit| match edit {
+      Edit::Same(_) => (current + 1, new + 1),
+      Edit::Removed(_) => (current + 1, new),
+      Edit::Added(_) => (current, new + 1),
+    });
+    let current_count = hunk.iter().filter(|edit| !matches!(edit, Edit::Added(_))).count();
+    let new_count = hunk.iter().filter(|edit| !matches!(edit, Edit::Removed(_))).count();
+    // Empty ranges start at the line preceding the hunk, like in `diff -u`.
+    let current_start = if current_count == 0 { current_start - 1 } else { current_start };
+    let new_start = if new_count == 0 { new_start - 1 } else { new_start };
+    text = text.cyan().s(format!("@@ -{current_start},{current_count} +{new_start},{new_count} @@\n")).reset();
+    for edit in hunk {
+      text = match edit {
+        Edit::Same(line) => text.s(format!(" {line}\n")),
+        Edit::Removed(line) => text.red().s(format!("-{line}\n")).reset(),
+        Edit::Added(line) => text.green().s(format!("+{line}\n")).reset(),
+      };
+    }
+  }
+  text
+}
+
+/// Single edit transforming the current lines into the new lines.
+enum Edit<'a> {
+  /// Line present in both, the current and the new content.
+  Same(&'a str),
+  /// Line present only in the current content.
+  Removed(&'a str),
+  /// Line present only in the new content.
+  Added(&'a str),
+}
+
+/// Returns edits transforming the current lines into the new lines, based on the longest common subsequence.
+fn edits<'a>(current: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+  // Lengths of the longest common subsequences of line suffixes.
+  let mut lengths = vec![vec![0_usize; new.len() + 1]; current.len() + 1];
+  for i in (0..current.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lengths[i][j] = if current[i] == new[j] {
+        lengths[i + 1][j + 1] + 1
+      } else {
+        lengths[i + 1][j].max(lengths[i][j + 1])
+      };
+    }
+  }
+  let (mut i, mut j) = (0, 0);
+  let mut edits = vec![];
+  while i < current.len() || j < new.len() {
+    if i < current.len() && j < new.len() && current[i] == new[j] {
+      edits.push(Edit::Same(current[i]));
+      i += 1;
+      j += 1;
+    } else if i < current.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+      edits.push(Edit::Removed(current[i]));
+      i += 1;
+    } else {
+      edits.push(Edit::Added(new[j]));
+      j += 1;
+    }
+  }
+  edits
+}
@@ -1,4 +1,17 @@
+use std::path::Path;
+
+mod test_api;
+mod test_changelog;
+mod test_ci;
 mod test_cli;
+mod test_code_of_conduct;
+mod test_community;
+mod test_headers;
+mod test_licenses;
+mod test_lint;
+mod test_readme;
+mod test_scaffold;
+mod test_workspace;
 
 #[cfg(not(target_os = "windows"))]
 fn normalize_exe(s: &str) -> String {
@@ -9,3 +22,35 @@ fn normalize_exe(s: &str) -> String {
 fn normalize_exe(s: &str) -> String {
   s.replace("||EXE||", ".exe")
 }
+
+/// Copies the fixture directory into a new temporary directory.
+fn fixture(dir: impl AsRef<Path>) -> tempfile::TempDir {
+  fn copy(source: &Path, target: &Path) {
+    for entry in std::fs::read_dir(source).unwrap() {
+      let path = entry.unwrap().path();
+      let target_path = target.join(path.file_name().unwrap());
+      if path.is_dir() {
+        std::fs::create_dir_all(&target_path).unwrap();
+        copy(&path, &target_path);
+      } else {
+        std::fs::copy(&path, &target_path).unwrap();
+      }
+    }
+  }
+  let temp_dir = tempfile::TempDir::new().unwrap();
+  copy(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join(dir), temp_dir.path());
+  temp_dir
+}
+
+/// Copies the fixture package into a new temporary directory and edits its `Cargo.toml` file.
+fn package(dir: impl AsRef<Path>, edit: impl FnOnce(String) -> String) -> tempfile::TempDir {
+  let temp_dir = fixture(dir);
+  let manifest = edit(read(&temp_dir, "Cargo.toml"));
+  std::fs::write(temp_dir.path().join("Cargo.toml"), manifest).unwrap();
+  temp_dir
+}
+
+/// Reads the content of the file in specified directory.
+fn read(dir: &tempfile::TempDir, file_name: &str) -> String {
+  std::fs::read_to_string(dir.path().join(file_name)).unwrap()
+}
@@ -0,0 +1,4 @@
+use super::*;
+
+mod test_generators;
+mod test_release;
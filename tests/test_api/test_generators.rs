@@ -0,0 +1,63 @@
+use super::*;
+use magg::manifest::Manifest;
+use magg::writer::{Change, Writer};
+use magg::{code_of_conduct, licenses, readme};
+
+#[test]
+fn readme() {
+  let dir = fixture("test_code_of_conduct/package");
+  let manifest = Manifest::load(dir.path()).unwrap();
+  let readmes = readme::scaffold_readmes(&manifest, &readme::Options::new().body("docs/README.md")).unwrap();
+  assert_eq!(1, readmes.len());
+  assert_eq!("README.md", readmes[0].0);
+  assert!(readmes[0].1.starts_with("### basic\n"));
+  assert!(!dir.path().join("README.md").exists());
+}
+
+#[test]
+fn licenses() {
+  let dir = fixture("test_licenses/package");
+  let manifest = Manifest::load(dir.path()).unwrap();
+  let files = licenses::get_license_files(&manifest).unwrap();
+  let file_names = files.iter().map(|(file_name, _)| file_name.as_str()).collect::<Vec<&str>>();
+  assert_eq!(vec!["LICENSE-MIT", "LICENSE", "NOTICE"], file_names);
+  assert!(!dir.path().join("LICENSE").exists());
+}
+
+#[test]
+fn code_of_conduct_with_writer() {
+  let dir = fixture("test_code_of_conduct/package");
+  let manifest = Manifest::load(dir.path()).unwrap();
+  let contents = code_of_conduct::get_code_of_conduct(&manifest).unwrap();
+  let mut writer = Writer::new(false, false, dir.path());
+  let (change, diff) = writer.write(dir.path().join("CODE_OF_CONDUCT.md"), &contents).unwrap();
+  assert_eq!(Change::Created, change);
+  assert!(diff.is_none());
+  assert_eq!(contents, read(&dir, "CODE_OF_CONDUCT.md"));
+}
+
+#[test]
+fn error_kind() {
+  let dir = tempfile::TempDir::new().unwrap();
+  let Err(reason) = Manifest::load(dir.path()) else {
+    panic!("manifest should not be found");
+  };
+  assert!(matches!(reason, magg::errors::MaggError::Manifest { .. }));
+  assert_eq!(4, reason.exit_code());
+}
+
+#[test]
+fn documented_exit_codes() {
+  use magg::errors::{EXIT_CODES, MaggError};
+  let errors = [
+    MaggError::io("failed", std::io::Error::other("reason")),
+    MaggError::manifest("invalid"),
+    MaggError::parse("invalid"),
+    MaggError::validation("invalid"),
+    MaggError::forge("failed"),
+    MaggError::NotOverwritten { paths: vec![] },
+  ];
+  for error in errors {
+    assert!(EXIT_CODES.iter().any(|(code, _)| *code == error.exit_code()), "undocumented exit code of: {error}");
+  }
+}
@@ -0,0 +1,214 @@
+use magg::release::{self, Options, is_prerelease};
+use std::thread::JoinHandle;
+
+/// Request received by the mock forge.
+struct Received {
+  /// Request method.
+  method: String,
+  /// Request path with query.
+  url: String,
+  /// Request headers with lowercase names.
+  headers: Vec<(String, String)>,
+  /// Request body.
+  body: Vec<u8>,
+}
+
+impl Received {
+  /// Returns the value of the header.
+  fn header(&self, name: &str) -> Option<&str> {
+    self.headers.iter().find(|(header, _)| header == name).map(|(_, value)| value.as_str())
+  }
+
+  /// Returns the body parsed as JSON.
+  fn json(&self) -> serde_json::Value {
+    serde_json::from_slice(&self.body).unwrap()
+  }
+}
+
+/// Starts the mock forge answering requests with the responses in order, `{url}` in responses is replaced with the URL of the mock forge.
+/// Returns the URL of the mock forge and the handle returning received requests.
+fn mock_forge(responses: &[(u16, &str)]) -> (String, JoinHandle<Vec<Received>>) {
+  let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+  let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+  let responses = responses
+    .iter()
+    .map(|(status, body)| (*status, body.replace("{url}", &url)))
+    .collect::<Vec<(u16, String)>>();
+  let handle = std::thread::spawn(move || {
+    let mut received = vec![];
+    for (status, body) in responses {
+      let mut request = server.recv().unwrap();
+      let mut request_body = vec![];
+      request.as_reader().read_to_end(&mut request_body).unwrap();
+      received.push(Received {
+        method: request.method().to_string(),
+        url: request.url().to_string(),
+        headers: request
+          .headers()
+          .iter()
+          .map(|header| (header.field.as_str().as_str().to_lowercase(), header.value.as_str().to_string()))
+          .collect(),
+        body: request_body,
+      });
+      request.respond(tiny_http::Response::from_string(body).with_status_code(status)).unwrap();
+    }
+    received
+  });
+  (url, handle)
+}
+
+/// Returns the temporary directory with the file attached to releases.
+fn asset() -> tempfile::TempDir {
+  let dir = tempfile::TempDir::new().unwrap();
+  std::fs::write(dir.path().join("magg.tar.gz"), "archive").unwrap();
+  dir
+}
+
+#[test]
+fn create_prerelease() {
+  let dir = asset();
+  let (url, handle) = mock_forge(&[
+    (404, r#"{"message":"Not Found"}"#),
+    (
+      201,
+      r#"{"id":1,"html_url":"https://github.com/EngosSoftware/demo/releases/tag/v1.0.0-rc.1","upload_url":"{url}/uploads/repos/EngosSoftware/demo/releases/1/assets{?name,label}","assets":[]}"#,
+    ),
+    (201, r#"{"id":3,"name":"magg.tar.gz"}"#),
+  ]);
+  let options = Options::new("https://github.com/EngosSoftware/demo", "v1.0.0-rc.1")
+    .notes("## Features\n")
+    .asset(dir.path().join("magg.tar.gz"))
+    .api_url(url)
+    .token("secret");
+  let release = release::publish(&options).unwrap();
+  assert!(release.created);
+  assert!(release.prerelease);
+  assert_eq!("https://github.com/EngosSoftware/demo/releases/tag/v1.0.0-rc.1", release.url);
+  assert_eq!(vec!["magg.tar.gz"], release.assets);
+  let received = handle.join().unwrap();
+  assert_eq!(
+    "GET /repos/EngosSoftware/demo/releases/tags/v1.0.0-rc.1",
+    format!("{} {}", received[0].method, received[0].url)
+  );
+  assert_eq!(Some("Bearer secret"), received[0].header("authorization"));
+  assert_eq!("POST /repos/EngosSoftware/demo/releases", format!("{} {}", received[1].method, received[1].url));
+  let body = received[1].json();
+  assert_eq!("v1.0.0-rc.1", body["tag_name"]);
+  assert_eq!("## Features\n", body["body"]);
+  assert_eq!(true, body["prerelease"]);
+  assert_eq!(
+    "POST /uploads/repos/EngosSoftware/demo/releases/1/assets?name=magg.tar.gz",
+    format!("{} {}", received[2].method, received[2].url)
+  );
+  assert_eq!(Some("application/octet-stream"), received[2].header("content-type"));
+  assert_eq!(b"archive", received[2].body.as_slice());
+}
+
+#[test]
+fn update_release() {
+  let dir = asset();
+  let release_json =
+    r#"{"id":5,"html_url":"https://github.com/EngosSoftware/demo/releases/tag/v1.0.0","upload_url":"{url}/uploads/assets{?name,label}","assets":[{"id":9,"name":"magg.tar.gz"}]}"#;
+  let (url, handle) = mock_forge(&[(200, release_json), (200, release_json), (204, ""), (201, r#"{"id":10,"name":"magg.tar.gz"}"#)]);
+  let options = Options::new("git@github.com:EngosSoftware/demo.git", "v1.0.0")
+    .notes("## Fixes\n")
+    .asset(dir.path().join("magg.tar.gz"))
+    .api_url(url)
+    .token("secret");
+  let release = release::publish(&options).unwrap();
+  assert!(!release.created);
+  assert!(!release.prerelease);
+  let requests = handle
+    .join()
+    .unwrap()
+    .iter()
+    .map(|received| format!("{} {}", received.method, received.url))
+    .collect::<Vec<String>>();
+  assert_eq!(
+    vec![
+      "GET /repos/EngosSoftware/demo/releases/tags/v1.0.0",
+      "PATCH /repos/EngosSoftware/demo/releases/5",
+      "DELETE /repos/EngosSoftware/demo/releases/assets/9",
+      "POST /uploads/assets?name=magg.tar.gz",
+    ],
+    requests
+  );
+}
+
+#[test]
+fn gitlab_release() {
+  let dir = asset();
+  let (url, handle) = mock_forge(&[
+    (404, r#"{"message":"404 Not Found"}"#),
+    (
+      201,
+      r#"{"tag_name":"v1.0.0","_links":{"self":"https://gitlab.com/EngosSoftware/demo/-/releases/v1.0.0"},"assets":{"links":[]}}"#,
+    ),
+    (201, r#"{"url":"/uploads/abc/magg.tar.gz"}"#),
+    (201, r#"{"id":1,"name":"magg.tar.gz"}"#),
+  ]);
+  let options = Options::new("https://gitlab.com/EngosSoftware/demo", "v1.0.0")
+    .notes("## Features\n")
+    .asset(dir.path().join("magg.tar.gz"))
+    .api_url(url)
+    .token("secret");
+  let release = release::publish(&options).unwrap();
+  assert!(release.created);
+  assert_eq!("https://gitlab.com/EngosSoftware/demo/-/releases/v1.0.0", release.url);
+  let received = handle.join().unwrap();
+  assert_eq!(Some("secret"), received[0].header("private-token"));
+  assert_eq!("POST /projects/EngosSoftware%2Fdemo/releases", format!("{} {}", received[1].method, received[1].url));
+  assert_eq!("## Features\n", received[1].json()["description"]);
+  assert_eq!("POST /projects/EngosSoftware%2Fdemo/uploads", format!("{} {}", received[2].method, received[2].url));
+  assert!(String::from_utf8_lossy(&received[2].body).contains("name=\"file\"; filename=\"magg.tar.gz\""));
+  assert_eq!(
+    "POST /projects/EngosSoftware%2Fdemo/releases/v1.0.0/assets/links",
+    format!("{} {}", received[3].method, received[3].url)
+  );
+  assert_eq!("https://gitlab.com/EngosSoftware/demo/uploads/abc/magg.tar.gz", received[3].json()["url"]);
+}
+
+#[test]
+fn gitlab_prerelease_not_marked() {
+  let (url, handle) = mock_forge(&[
+    (404, r#"{"message":"404 Not Found"}"#),
+    (
+      201,
+      r#"{"tag_name":"v1.0.0-rc.1","_links":{"self":"https://gitlab.com/EngosSoftware/demo/-/releases/v1.0.0-rc.1"},"assets":{"links":[]}}"#,
+    ),
+  ]);
+  let options = Options::new("https://gitlab.com/EngosSoftware/demo", "v1.0.0-rc.1").api_url(url).token("secret");
+  let release = release::publish(&options).unwrap();
+  assert!(!release.prerelease);
+  let received = handle.join().unwrap();
+  assert_eq!(serde_json::Value::Null, received[1].json()["prerelease"]);
+}
+
+#[test]
+fn failed_request() {
+  let (url, handle) = mock_forge(&[(401, r#"{"message":"Bad credentials"}"#)]);
+  let options = Options::new("https://github.com/EngosSoftware/demo", "v1.0.0").api_url(url).token("invalid");
+  let error = release::publish(&options).unwrap_err();
+  assert_eq!(9, error.exit_code());
+  assert!(error.to_string().ends_with("/releases/tags/v1.0.0, status 401: {\"message\":\"Bad credentials\"}"));
+  handle.join().unwrap();
+}
+
+#[test]
+fn missing_asset() {
+  let options = Options::new("https://github.com/EngosSoftware/demo", "v1.0.0")
+    .asset("missing.tar.gz")
+    .api_url("http://127.0.0.1:9")
+    .token("secret");
+  assert_eq!(3, release::publish(&options).unwrap_err().exit_code());
+}
+
+#[test]
+fn prerelease_tags() {
+  assert!(is_prerelease("v1.0.0-rc.1"));
+  assert!(is_prerelease("0.2.0-alpha+build.5"));
+  assert!(is_prerelease("magg-v1.2.3-beta"));
+  assert!(!is_prerelease("v1.0.0"));
+  assert!(!is_prerelease("v1.0.0+build-5"));
+  assert!(!is_prerelease("release-2024"));
+}
@@ -0,0 +1,112 @@
+#[cfg(unix)]
+mod test_cache;
+mod test_interactive;
+mod test_options;
+#[cfg(unix)]
+mod test_parsing;
+#[cfg(unix)]
+mod test_release;
+
+/// Runs Git command in the specified directory.
+fn git(dir: &tempfile::TempDir, args: &[&str]) {
+  let status = std::process::Command::new("git")
+    .args(["-c", "user.name=Tester", "-c", "user.email=tester@engos.de"])
+    .args(args)
+    .current_dir(dir.path())
+    .output()
+    .unwrap()
+    .status;
+  assert!(status.success(), "git {} failed", args.join(" "));
+}
+
+/// Returns the temporary Git repository with tags `v0.1.0` and `v0.2.0`.
+fn repository() -> tempfile::TempDir {
+  let dir = tempfile::TempDir::new().unwrap();
+  git(&dir, &["init", "--quiet"]);
+  git(&dir, &["remote", "add", "origin", "git@github.com:EngosSoftware/demo.git"]);
+  for tag in ["v0.1.0", "v0.2.0"] {
+    git(&dir, &["commit", "--quiet", "--allow-empty", "--message", tag]);
+    git(&dir, &["tag", tag]);
+  }
+  dir
+}
+
+/// Script of the fake `gh` command, answering with JSON files from its directory and logging its arguments.
+#[cfg(unix)]
+const FAKE_GH: &str = r#"#!/bin/sh
+dir=$(dirname "$0")
+echo "$*" >> "$dir/calls.log"
+case "$1 $2" in
+  "issue list") cat "$dir/issues.json" ;;
+  "pr list") cat "$dir/pulls.json" ;;
+  "pr view") cat "$dir/pull-$3.json" ;;
+  *) exit 1 ;;
+esac
+"#;
+
+/// Fake `gh` command placed first on `PATH` of the changelog command.
+#[cfg(unix)]
+struct FakeGh {
+  /// Directory containing the fake command, its responses and the log of calls.
+  dir: tempfile::TempDir,
+  /// Cache directory of the changelog command.
+  cache_dir: tempfile::TempDir,
+}
+
+#[cfg(unix)]
+impl FakeGh {
+  /// Creates the fake `gh` command without issues and pull requests.
+  fn new() -> Self {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("gh");
+    std::fs::write(&path, FAKE_GH).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let fake = Self {
+      dir,
+      cache_dir: tempfile::TempDir::new().unwrap(),
+    };
+    fake.respond("issues.json", "[]");
+    fake.respond("pulls.json", "[]");
+    fake
+  }
+
+  /// Sets the response stored in the file with specified name.
+  fn respond(&self, file_name: &str, content: &str) {
+    std::fs::write(self.dir.path().join(file_name), content).unwrap();
+  }
+
+  /// Returns arguments of all calls of the fake command and clears the log of calls.
+  fn calls(&self) -> Vec<String> {
+    let path = self.dir.path().join("calls.log");
+    let calls = std::fs::read_to_string(&path).unwrap_or_default().lines().map(|line| line.to_string()).collect();
+    _ = std::fs::remove_file(path);
+    calls
+  }
+
+  /// Runs the changelog command in the repository with specified additional arguments,
+  /// returns the exit code, the standard output and the standard error.
+  fn changelog(&self, repository: &tempfile::TempDir, args: &[&str]) -> (i32, String, String) {
+    let path = format!("{}:{}", self.dir.path().display(), std::env::var("PATH").unwrap_or_default());
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_magg"))
+      .args(["changelog", "--start", "v0.2.0", "--end", "HEAD", "--milestone", "0.3.0", "--repo", "EngosSoftware/demo"])
+      .args(args)
+      .current_dir(repository.path())
+      .env("PATH", path)
+      .env("XDG_CACHE_HOME", self.cache_dir.path())
+      .output()
+      .unwrap();
+    (
+      output.status.code().unwrap_or(-1),
+      String::from_utf8_lossy(&output.stdout).to_string(),
+      String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+  }
+}
+
+/// Returns the hash of the commit with specified revision.
+#[cfg(unix)]
+fn commit_hash(dir: &tempfile::TempDir, revision: &str) -> String {
+  let output = std::process::Command::new("git").args(["rev-parse", revision]).current_dir(dir.path()).output().unwrap();
+  String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
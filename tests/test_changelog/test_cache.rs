@@ -0,0 +1,71 @@
+use super::*;
+
+/// Response of `gh pr list` with the pull request #5 updated at the specified time.
+fn pulls(updated_at: &str) -> String {
+  format!(r#"[{{"number":5,"title":"Add feature","url":"https://github.com/EngosSoftware/demo/pull/5","updatedAt":"{updated_at}"}}]"#)
+}
+
+/// Returns the repository with the commit of the pull request #5 and the fake `gh` command answering with this pull request.
+fn setup() -> (tempfile::TempDir, FakeGh) {
+  let dir = repository();
+  git(&dir, &["commit", "--quiet", "--allow-empty", "--message", "Implement feature"]);
+  let gh = FakeGh::new();
+  gh.respond("pulls.json", &pulls("2026-10-01T10:00:00Z"));
+  gh.respond(
+    "pull-5.json",
+    &format!(r#"[{{"oid":"{}","messageHeadline":"Implement feature"}}]"#, commit_hash(&dir, "HEAD")),
+  );
+  (dir, gh)
+}
+
+/// Returns the number of calls retrieving commits of pull requests.
+fn pr_view_calls(gh: &FakeGh) -> usize {
+  gh.calls().iter().filter(|call| call.starts_with("pr view ")).count()
+}
+
+#[test]
+fn unchanged_pull_request_taken_from_cache() {
+  let (dir, gh) = setup();
+  let (code, first, _) = gh.changelog(&dir, &[]);
+  assert_eq!(0, code);
+  assert_eq!(1, pr_view_calls(&gh));
+  let (code, second, _) = gh.changelog(&dir, &[]);
+  assert_eq!(0, code);
+  assert_eq!(0, pr_view_calls(&gh));
+  assert_eq!(first, second);
+  assert_eq!("- Add feature ([#5])\n\n[#5]: https://github.com/EngosSoftware/demo/pull/5\n", second);
+}
+
+#[test]
+fn updated_pull_request_retrieved_again() {
+  let (dir, gh) = setup();
+  assert_eq!(0, gh.changelog(&dir, &[]).0);
+  assert_eq!(1, pr_view_calls(&gh));
+  gh.respond("pulls.json", &pulls("2026-10-02T08:30:00Z"));
+  assert_eq!(0, gh.changelog(&dir, &[]).0);
+  assert_eq!(1, pr_view_calls(&gh));
+  assert_eq!(0, gh.changelog(&dir, &[]).0);
+  assert_eq!(0, pr_view_calls(&gh));
+}
+
+#[test]
+fn no_cache() {
+  let (dir, gh) = setup();
+  for _ in 0..2 {
+    assert_eq!(0, gh.changelog(&dir, &["--no-cache"]).0);
+    assert_eq!(1, pr_view_calls(&gh));
+  }
+  assert_eq!(0, gh.changelog(&dir, &[]).0);
+  assert_eq!(1, pr_view_calls(&gh));
+}
+
+#[test]
+fn issues_and_pull_requests_listed_every_run() {
+  let (dir, gh) = setup();
+  for _ in 0..2 {
+    assert_eq!(0, gh.changelog(&dir, &[]).0);
+    let calls = gh.calls();
+    assert!(calls.iter().any(|call| call.starts_with("issue list ")));
+    assert!(calls.iter().any(|call| call.starts_with("pr list ")));
+  }
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn cancelled() {
+  let dir = repository();
+  let mut command = cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("changelog")
+    .arg("-i")
+    .arg("-q")
+    .stdin("\n2\n\n0.2.0\nn\n")
+    .code(8)
+    .stdout("");
+  command.execute();
+  let stderr = command.get_stderr();
+  assert!(stderr.starts_with("Repository [EngosSoftware/demo]: "));
+  assert!(stderr.contains("  1) v0.2.0\n  2) v0.1.0\nStart revision [v0.2.0]: "));
+  assert!(stderr.contains("  1) HEAD\n  2) v0.2.0\n  3) v0.1.0\nEnd revision [HEAD]: "));
+  assert!(stderr.contains("Changelog of EngosSoftware/demo, commits v0.1.0...HEAD, milestone 0.2.0\nGenerate changelog? [Y/n]: "));
+  assert!(stderr.ends_with("error: changelog generation cancelled\n"));
+}
+
+#[test]
+fn specified_values_as_defaults() {
+  let dir = repository();
+  let mut command = cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("changelog")
+    .arg("--interactive")
+    .arg("--repo")
+    .arg("EngosSoftware/other")
+    .arg("--milestone")
+    .arg("1.0.0")
+    .arg("-q")
+    .stdin("\n\n\n\nno\n")
+    .code(8)
+    .stdout("");
+  command.execute();
+  assert!(command.get_stderr().contains("Changelog of EngosSoftware/other, commits v0.2.0...HEAD, milestone 1.0.0\n"));
+}
+
+#[test]
+fn required_values() {
+  cli_assert::command!().arg("changelog").arg("--start").arg("v0.1.0").code(2).stdout("").execute();
+}
@@ -0,0 +1,47 @@
+#[test]
+fn zero_jobs() {
+  let mut command = cli_assert::command!()
+    .arg("changelog")
+    .arg("--start")
+    .arg("v0.1.0")
+    .arg("--end")
+    .arg("v0.2.0")
+    .arg("--milestone")
+    .arg("0.2.0")
+    .arg("--repo")
+    .arg("EngosSoftware/demo")
+    .arg("--jobs")
+    .arg("0")
+    .code(2)
+    .stdout("");
+  command.execute();
+  assert!(command.get_stderr().contains("error: invalid value '0' for '--jobs <N>': 0 is not in 1..=65535\n"));
+}
+
+#[test]
+fn invalid_jobs() {
+  cli_assert::command!()
+    .arg("changelog")
+    .arg("--repo")
+    .arg("EngosSoftware/demo")
+    .arg("-j")
+    .arg("many")
+    .arg("--no-cache")
+    .code(2)
+    .stdout("")
+    .execute();
+}
+
+#[test]
+fn attach_without_release() {
+  let mut command = cli_assert::command!()
+    .arg("changelog")
+    .arg("--repo")
+    .arg("EngosSoftware/demo")
+    .arg("--attach")
+    .arg("magg.tar.gz")
+    .code(2)
+    .stdout("");
+  command.execute();
+  assert!(command.get_stderr().contains("--publish-release <TAG>"));
+}
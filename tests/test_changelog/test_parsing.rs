@@ -0,0 +1,78 @@
+use super::*;
+
+/// Returns the repository with commits after tag `v0.2.0`, the last one belongs to the pull request #5.
+fn repository_with_commits() -> tempfile::TempDir {
+  let dir = repository();
+  for subject in [r#"Split on "|||" and 'quotes'"#, "Fix docs (#7)", "Implement feature"] {
+    git(&dir, &["commit", "--quiet", "--allow-empty", "--message", subject]);
+  }
+  dir
+}
+
+#[test]
+fn titles_with_separators_and_quotes() {
+  let dir = repository_with_commits();
+  let feature = commit_hash(&dir, "HEAD");
+  let docs = commit_hash(&dir, "HEAD~1");
+  let split = commit_hash(&dir, "HEAD~2");
+  let gh = FakeGh::new();
+  gh.respond(
+    "issues.json",
+    r#"[{"number":3,"title":"Fix \"quoted\" ||| issue","url":"https://github.com/EngosSoftware/demo/issues/3"}]"#,
+  );
+  gh.respond(
+    "pulls.json",
+    r#"[{"number":5,"title":"Add 'quoted' ||| feature","url":"https://github.com/EngosSoftware/demo/pull/5","updatedAt":"2026-10-01T10:00:00Z"}]"#,
+  );
+  gh.respond("pull-5.json", &format!(r#"[{{"oid":"{feature}","messageHeadline":"Implement feature"}}]"#));
+  let (code, stdout, stderr) = gh.changelog(&dir, &[]);
+  assert_eq!(0, code, "{stderr}");
+  assert!(stdout.starts_with("- Fix \"quoted\" ||| issue ([#3])\n- Add 'quoted' ||| feature ([#5])\n"));
+  assert!(stdout.contains(&format!("- Split on \"|||\" and 'quotes' ([0x{}])\n", &split[..7])));
+  assert!(stdout.contains(&format!("- Fix docs (#7) ([0x{}])\n", &docs[..7])));
+  assert!(!stdout.contains("Implement feature"));
+  assert!(stdout.contains("\n[#3]: https://github.com/EngosSoftware/demo/issues/3\n[#5]: https://github.com/EngosSoftware/demo/pull/5\n"));
+  assert!(stdout.contains(&format!("[0x{}]: https://github.com/EngosSoftware/demo/commit/{split}\n", &split[..7])));
+  assert!(!stdout.contains("WARNINGS"));
+  assert_eq!("warning: PR: #7 not in milestone 0.3.0 | Fix docs (#7)\n", stderr);
+  assert!(gh.calls().contains(&"pr view 5 --repo=EngosSoftware/demo --json=commits --jq=.commits".to_string()));
+}
+
+#[test]
+fn malformed_record() {
+  let dir = repository_with_commits();
+  let gh = FakeGh::new();
+  gh.respond(
+    "issues.json",
+    r#"[{"number":3,"title":"Fix","url":"https://github.com/EngosSoftware/demo/issues/3"},{"number":"4","title":"Broken"}]"#,
+  );
+  let (code, stdout, stderr) = gh.changelog(&dir, &[]);
+  assert_eq!(7, code);
+  assert_eq!("", stdout);
+  assert_eq!(
+    "error: invalid issue record: {\"number\":\"4\",\"title\":\"Broken\"}, reason: invalid type: string \"4\", expected u64\n",
+    stderr
+  );
+}
+
+#[test]
+fn invalid_json() {
+  let dir = repository_with_commits();
+  let gh = FakeGh::new();
+  gh.respond("pulls.json", "number ||| title ||| url");
+  let (code, _, stderr) = gh.changelog(&dir, &[]);
+  assert_eq!(7, code);
+  assert_eq!("error: failed to parse pull request records, reason: expected ident at line 1 column 3\n", stderr);
+}
+
+#[test]
+fn commits_of_fixture_repository() {
+  let dir = repository_with_commits();
+  let gh = FakeGh::new();
+  let (code, stdout, stderr) = gh.changelog(&dir, &["--exclude-commit", "Fix docs"]);
+  assert_eq!(0, code, "{stderr}");
+  let subjects = stdout.lines().filter(|line| line.starts_with("- ")).collect::<Vec<&str>>();
+  assert_eq!(2, subjects.len());
+  assert!(subjects.iter().any(|line| line.starts_with("- Split on \"|||\" and 'quotes' ([0x")));
+  assert!(subjects.iter().any(|line| line.starts_with("- Implement feature ([0x")));
+}
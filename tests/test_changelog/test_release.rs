@@ -0,0 +1,33 @@
+use super::*;
+
+#[test]
+fn dry_run_sends_no_requests() {
+  let dir = repository();
+  let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+  let api_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+  let gh = FakeGh::new();
+  let (code, _, stderr) = gh.changelog(
+    &dir,
+    &["--publish-release", "v0.3.0-rc.1", "--attach", "dist/magg.tar.gz", "--api-url", &api_url, "--dry-run"],
+  );
+  assert_eq!(0, code, "{stderr}");
+  assert_eq!(
+    "would publish prerelease v0.3.0-rc.1: https://github.com/EngosSoftware/demo\nwould attach: magg.tar.gz\n",
+    stderr
+  );
+  assert!(server.try_recv().unwrap().is_none());
+}
+
+#[test]
+fn unknown_forge() {
+  let dir = repository();
+  git(&dir, &["remote", "set-url", "origin", "https://gitlab.com/EngosSoftware/other.git"]);
+  let gh = FakeGh::new();
+  let (code, stdout, stderr) = gh.changelog(&dir, &[]);
+  assert_eq!(8, code);
+  assert_eq!("", stdout);
+  assert_eq!(
+    "error: forge of repository EngosSoftware/demo cannot be determined, Git remote 'origin' does not point to it\n",
+    stderr
+  );
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+#[test]
+fn generate() {
+  let dir = fixture("test_ci/package");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("ci")
+    .code(0)
+    .stdout("created: .github/workflows/build-linux.yml\ncreated: .github/workflows/build-windows.yml\ncreated: .github/workflows/build-macos.yml\ncreated: .github/workflows/build-macos-arm64.yml\n4 created, 0 updated, 0 unchanged\n")
+    .stderr("warning: README.md:9: badge points at missing workflow file '.github/workflows/audit.yml'\n")
+    .execute();
+  let linux = read(&dir, ".github/workflows/build-linux.yml");
+  assert!(linux.starts_with("name: Linux\n"));
+  assert!(linux.contains("          toolchain: \"stable\"\n          targets: x86_64-unknown-linux-gnu\n"));
+  assert!(linux.contains("\n  fmt:\n"));
+  assert!(linux.contains("      - run: cargo llvm-cov --no-cfg-coverage\n"));
+  assert!(linux.contains("\n  msrv:\n    runs-on: ubuntu-latest\n"));
+  assert!(linux.contains("          toolchain: \"1.85\"\n"));
+  let windows = read(&dir, ".github/workflows/build-windows.yml");
+  assert!(windows.contains("    runs-on: windows-latest\n"));
+  assert!(!windows.contains("\n  fmt:\n"));
+  assert!(read(&dir, ".github/workflows/build-macos.yml").contains("          targets: x86_64-apple-darwin\n"));
+  assert!(read(&dir, ".github/workflows/build-macos-arm64.yml").contains("          targets: aarch64-apple-darwin\n"));
+}
+
+#[test]
+fn toolchain_file() {
+  let dir = package("test_ci/package", |manifest| manifest.replace("rust-version = \"1.85\"\n", ""));
+  std::fs::write(dir.path().join("rust-toolchain.toml"), "[toolchain]\nchannel = \"nightly-2026-01-01\"\n").unwrap();
+  cli_assert::command!().current_dir(dir.path()).arg("ci").code(0).stdout("created: .github/workflows/build-linux.yml\ncreated: .github/workflows/build-windows.yml\ncreated: .github/workflows/build-macos.yml\ncreated: .github/workflows/build-macos-arm64.yml\n4 created, 0 updated, 0 unchanged\n").execute();
+  let linux = read(&dir, ".github/workflows/build-linux.yml");
+  assert!(linux.contains("          toolchain: \"nightly-2026-01-01\"\n"));
+  assert!(!linux.contains("\n  msrv:\n"));
+}
+
+#[test]
+fn check() {
+  let dir = fixture("test_ci/package");
+  let expected = r#"missing file: .github/workflows/build-linux.yml
+missing file: .github/workflows/build-windows.yml
+missing file: .github/workflows/build-macos.yml
+missing file: .github/workflows/build-macos-arm64.yml
+README.md:7: badge points at missing workflow file '.github/workflows/build-linux.yml'
+README.md:8: badge points at missing workflow file '.github/workflows/build-windows.yml'
+README.md:9: badge points at missing workflow file '.github/workflows/audit.yml'
+"#;
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("ci")
+    .arg("--check")
+    .code(1)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+  std::fs::create_dir_all(dir.path().join(".github/workflows")).unwrap();
+  std::fs::write(dir.path().join(".github/workflows/audit.yml"), "name: Audit\n").unwrap();
+  cli_assert::command!().current_dir(dir.path()).arg("ci").code(0).stdout("created: .github/workflows/build-linux.yml\ncreated: .github/workflows/build-windows.yml\ncreated: .github/workflows/build-macos.yml\ncreated: .github/workflows/build-macos-arm64.yml\n4 created, 0 updated, 0 unchanged\n").stderr("").execute();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("ci")
+    .arg("--check")
+    .code(0)
+    .stdout("")
+    .stderr("")
+    .execute();
+}
@@ -1,4 +1,9 @@
 use super::*;
 
+mod test_dry_run;
+mod test_exit_codes;
 mod test_help;
 mod test_no_arguments;
+mod test_overwrite;
+mod test_packaging;
+mod test_verbosity;
@@ -0,0 +1,64 @@
+use super::*;
+
+#[test]
+fn diff_of_changed_file() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .code(0)
+    .stdout("created: SUPPORT.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let changed = read(&dir, "SUPPORT.md").replace("Start with the [README](README.md)", "Start with the README");
+  std::fs::write(dir.path().join("SUPPORT.md"), &changed).unwrap();
+  let expected = r#"--- SUPPORT.md
++++ SUPPORT.md
+@@ -2,7 +2,7 @@
+ 
+ ## Documentation
+ 
+-Start with the README and the [API documentation](https://docs.rs/basic).
++Start with the [README](README.md) and the [API documentation](https://docs.rs/basic).
+ 
+ ## Questions and bug reports
+ 
+not overwritten: SUPPORT.md
+0 created, 0 updated, 0 unchanged, 1 not overwritten (dry run)
+"#;
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .arg("--dry-run")
+    .code(0)
+    .stdout(expected)
+    .stderr("warning: 1 file was not overwritten because of changed content, use --force to overwrite\n")
+    .execute();
+  assert_eq!(changed, read(&dir, "SUPPORT.md"));
+}
+
+#[test]
+fn nothing_written() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("contributing")
+    .arg("--dry-run")
+    .code(0)
+    .stderr("")
+    .execute();
+  assert!(!dir.path().join("CONTRIBUTING.md").exists());
+}
+
+#[test]
+fn unchanged_files() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!().current_dir(dir.path()).arg("support").code(0).stderr("").execute();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .code(0)
+    .stdout("0 created, 0 updated, 1 unchanged\n")
+    .stderr("")
+    .execute();
+}
@@ -0,0 +1,24 @@
+#[test]
+fn missing_manifest() {
+  let dir = tempfile::TempDir::new().unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .code(4)
+    .stdout("")
+    .stderr("error: failed to read file: Cargo.toml, reason: No such file or directory (os error 2)\nhint: run magg in the package directory or use --manifest-path\n")
+    .execute();
+}
+
+#[test]
+fn invalid_manifest() {
+  let dir = tempfile::TempDir::new().unwrap();
+  std::fs::write(dir.path().join("Cargo.toml"), "[package\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .code(4)
+    .stdout("")
+    .stderr("error: failed to parse file Cargo.toml, reason: TOML parse error at line 1, column 9\n  |\n1 | [package\n  |         ^\nunclosed table, expected `]`\n\n")
+    .execute();
+}
@@ -4,18 +4,116 @@ use super::*;
 fn short() {
   let expected = r#"Sophisticated tooling for Rust developers
 
-Usage: magg||EXE|| [COMMAND]
+Usage: magg||EXE|| [OPTIONS] [COMMAND]
 
 Commands:
   readme           Generates README.md file
-  licenses         Generates MIT and Apache 2.0 license files
+  licenses         Generates license files matching the license in Cargo.toml
   code-of-conduct  Generates code of conduct file
+  contributing     Generates CONTRIBUTING.md file
+  security         Generates SECURITY.md file
+  support          Generates SUPPORT.md file
+  issue-templates  Generates issue and pull request templates
+  ci               Generates CI workflow files matching build badges in README.md
   changelog        Generates changelog
+  headers          Adds or updates license headers in source files
+  lint             Checks documentation and manifest files
+  new              Creates a new crate repository in the specified directory
+  init             Creates a new crate repository in an existing directory
+  completions      Generates shell completions
+  man              Generates man pages
   help             Print this message or the help of the given subcommand(s)
 
 Options:
-  -V, --version  Print version
-  -h, --help     Print help
+  -V, --version               Print version
+      --workspace             Run the command for all workspace members
+  -p, --package <CRATE>       Run the command for the specified workspace member
+      --manifest-path <PATH>  Path to Cargo.toml of the package or the workspace
+      --output-dir <DIR>      Directory of generated files, defaults to the package directory
+  -v, --verbose...            Report executed commands and details on stderr, use -vv for debugging details
+  -q, --quiet                 Report only errors on stderr
+      --force                 Overwrite generated files even when their content has changed
+      --dry-run               Display changes of generated files without writing them
+  -h, --help                  Print help
+
+Exit codes:
+  0   success
+  1   check or lint found problems
+  2   invalid command-line arguments
+  3   reading or writing files failed
+  4   invalid or incomplete Cargo.toml
+  5   required command (git, gh, cargo) could not be started
+  6   command exited with failure
+  7   invalid input could not be parsed
+  8   invalid option or configuration value
+  9   forge (GitHub, GitLab, Gitea) API request failed
+  10  files were not overwritten because of changed content
 "#;
   cli_assert::command!().arg("-h").code(0).stdout(normalize_exe(expected)).stderr("").execute();
 }
+
+#[test]
+fn completions() {
+  let expected = r#"Generates shell completions
+
+Usage: magg||EXE|| completions [OPTIONS] <SHELL>
+
+Arguments:
+  <SHELL>  Shell to generate completions for [possible values: bash, elvish, fish, powershell, zsh]
+
+Options:
+      --workspace             Run the command for all workspace members
+  -p, --package <CRATE>       Run the command for the specified workspace member
+      --manifest-path <PATH>  Path to Cargo.toml of the package or the workspace
+      --output-dir <DIR>      Directory of generated files, defaults to the package directory
+  -v, --verbose...            Report executed commands and details on stderr, use -vv for debugging details
+  -q, --quiet                 Report only errors on stderr
+      --force                 Overwrite generated files even when their content has changed
+      --dry-run               Display changes of generated files without writing them
+  -h, --help                  Print help
+"#;
+  cli_assert::command!()
+    .arg("completions")
+    .arg("-h")
+    .code(0)
+    .stdout(normalize_exe(expected))
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn man() {
+  let expected = r#"Generates man pages of magg and its subcommands, only the magg.1 page is printed without --output-dir
+
+Usage: magg||EXE|| man [OPTIONS]
+
+Options:
+      --workspace
+          Run the command for all workspace members
+
+  -p, --package <CRATE>
+          Run the command for the specified workspace member
+
+      --manifest-path <PATH>
+          Path to Cargo.toml of the package or the workspace
+
+      --output-dir <DIR>
+          Directory of generated files, defaults to the package directory
+
+  -v, --verbose...
+          Report executed commands and details on stderr, use -vv for debugging details
+
+  -q, --quiet
+          Report only errors on stderr
+
+      --force
+          Overwrite generated files even when their content has changed
+
+      --dry-run
+          Display changes of generated files without writing them
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#;
+  cli_assert::command!().arg("man").arg("--help").code(0).stdout(normalize_exe(expected)).stderr("").execute();
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+#[test]
+fn regenerated_file_updated() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!().current_dir(dir.path()).arg("support").code(0).stderr("").execute();
+  assert!(read(&dir, ".magg-generated").ends_with("  SUPPORT.md\n"));
+  let manifest = read(&dir, "Cargo.toml").replace("name = \"basic\"", "name = \"renamed\"");
+  std::fs::write(dir.path().join("Cargo.toml"), manifest).unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .code(0)
+    .stdout("updated: SUPPORT.md\n0 created, 1 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, "SUPPORT.md").contains("https://docs.rs/renamed"));
+}
+
+#[test]
+fn unrecorded_file_adopted() {
+  let dir = fixture("test_community/package");
+  std::fs::write(dir.path().join("SUPPORT.md"), "# Help\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .code(0)
+    .stdout("updated: SUPPORT.md\n0 created, 1 updated, 0 unchanged\n")
+    .stderr("warning: file not generated before was overwritten: SUPPORT.md\n")
+    .execute();
+  assert!(read(&dir, "SUPPORT.md").contains("https://docs.rs/basic"));
+  assert!(read(&dir, ".magg-generated").ends_with("  SUPPORT.md\n"));
+}
+
+#[test]
+fn edited_file_refused() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!().current_dir(dir.path()).arg("support").code(0).stderr("").execute();
+  std::fs::write(dir.path().join("SUPPORT.md"), "# Support\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .code(10)
+    .stdout("not overwritten: SUPPORT.md\n0 created, 0 updated, 0 unchanged, 1 not overwritten\n")
+    .stderr("error: 1 file was not overwritten because of changed content, use --force to overwrite or --dry-run to display changes\n")
+    .execute();
+  assert_eq!("# Support\n", read(&dir, "SUPPORT.md"));
+}
+
+#[test]
+fn coverage_badge_untracked() {
+  let dir = fixture("test_readme/includes");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(0)
+    .stderr("")
+    .execute();
+  let readme = read(&dir, "README.md");
+  let badge = readme.lines().find(|line| line.starts_with("[cov-badge]: ")).unwrap();
+  std::fs::write(
+    dir.path().join("README.md"),
+    readme.replace(badge, "[cov-badge]: https://img.shields.io/badge/coverage-97%25-21b577.svg"),
+  )
+  .unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(0)
+    .stdout("0 created, 0 updated, 1 unchanged\n")
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn single_record() {
+  let dir = fixture("test_workspace/project");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .arg("--workspace")
+    .code(0)
+    .stdout("created: crates/alpha/SUPPORT.md\ncreated: crates/beta/SUPPORT.md\n2 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let record = read(&dir, ".magg-generated");
+  assert!(record.contains("  crates/alpha/SUPPORT.md\n"));
+  assert!(record.ends_with("  crates/beta/SUPPORT.md\n"));
+  assert!(!dir.path().join("crates/alpha/.magg-generated").exists());
+  cli_assert::command!().current_dir(dir.path()).arg("ci").arg("--workspace").code(0).stderr("").execute();
+  assert!(!dir.path().join(".github/workflows/.magg-generated").exists());
+  assert!(read(&dir, ".magg-generated").contains("  .github/workflows/build-linux.yml\n"));
+}
+
+#[test]
+fn unchanged_file_recorded() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!().current_dir(dir.path()).arg("support").code(0).stderr("").execute();
+  std::fs::remove_file(dir.path().join(".magg-generated")).unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .code(0)
+    .stdout("0 created, 0 updated, 1 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, ".magg-generated").ends_with("  SUPPORT.md\n"));
+}
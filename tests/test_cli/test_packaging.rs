@@ -0,0 +1,50 @@
+#[test]
+fn completions_written_to_output_dir() {
+  let dir = tempfile::TempDir::new().unwrap();
+  for (shell, file_name, start) in [
+    ("bash", "magg.bash", "_magg() {\n"),
+    ("zsh", "_magg", "#compdef magg\n"),
+    ("fish", "magg.fish", "# Print an optspec for argparse"),
+    ("powershell", "_magg.ps1", "\nusing namespace System.Management.Automation\n"),
+    ("elvish", "magg.elv", "\nuse builtin;\n"),
+  ] {
+    cli_assert::command!()
+      .arg("completions")
+      .arg(shell)
+      .arg("--output-dir")
+      .arg(dir.path())
+      .code(0)
+      .stdout(format!("created: {}\n1 created, 0 updated, 0 unchanged\n", dir.path().join(file_name).display()))
+      .stderr("")
+      .execute();
+    let completions = std::fs::read_to_string(dir.path().join(file_name)).unwrap();
+    assert!(completions.starts_with(start), "unexpected {shell} completions: {completions}");
+    assert!(completions.contains("changelog"));
+  }
+}
+
+#[test]
+fn invalid_shell() {
+  cli_assert::command!().arg("completions").arg("tcsh").code(2).stdout("").execute();
+}
+
+#[test]
+fn man_pages_written_to_output_dir() {
+  let dir = tempfile::TempDir::new().unwrap();
+  cli_assert::command!().arg("man").arg("--output-dir").arg(dir.path()).code(0).stderr("").execute();
+  let page = std::fs::read_to_string(dir.path().join("magg.1")).unwrap();
+  assert!(page.contains(".TH magg 1"));
+  assert!(page.contains("magg\\-changelog(1)"));
+  let page = std::fs::read_to_string(dir.path().join("magg-lint-markdown.1")).unwrap();
+  assert!(page.contains("magg\\-lint\\-markdown \\- Checks links and reference definitions in Markdown files"));
+}
+
+#[test]
+fn man_page_printed() {
+  let mut command = cli_assert::command!().arg("man").code(0).stderr("");
+  command.execute();
+  let page = command.get_stdout();
+  assert!(page.starts_with(".ie \\n(.g .ds Aq \\(aq\n"), "{page}");
+  assert_eq!(page.matches("\n.TH ").count(), 1);
+  assert!(page.contains(".TH magg 1"));
+}
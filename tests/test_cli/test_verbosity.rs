@@ -0,0 +1,29 @@
+use super::*;
+
+#[test]
+fn quiet_suppresses_warnings() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!().current_dir(dir.path()).arg("support").code(0).stderr("").execute();
+  std::fs::write(dir.path().join("SUPPORT.md"), "# Support\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .arg("--dry-run")
+    .arg("-q")
+    .code(0)
+    .stderr("")
+    .execute();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .arg("--quiet")
+    .code(10)
+    .stdout("not overwritten: SUPPORT.md\n0 created, 0 updated, 0 unchanged, 1 not overwritten\n")
+    .stderr("error: 1 file was not overwritten because of changed content, use --force to overwrite or --dry-run to display changes\n")
+    .execute();
+}
+
+#[test]
+fn quiet_conflicts_with_verbose() {
+  cli_assert::command!().arg("support").arg("-q").arg("-v").code(2).stdout("").execute();
+}
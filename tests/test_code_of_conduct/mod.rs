@@ -0,0 +1,15 @@
+use super::*;
+
+mod test_codes;
+
+/// Generates the code of conduct and returns its content.
+fn code_of_conduct(dir: &tempfile::TempDir) -> String {
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("code-of-conduct")
+    .code(0)
+    .stdout("created: CODE_OF_CONDUCT.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  read(dir, "CODE_OF_CONDUCT.md")
+}
@@ -0,0 +1,91 @@
+use super::*;
+
+#[test]
+fn default_code() {
+  let dir = fixture("test_code_of_conduct/package");
+  let content = code_of_conduct(&dir);
+  assert!(content.starts_with("# Contributor Covenant Code of Conduct\n"));
+  assert!(content.contains("version 2.1, available at"));
+  assert!(content.contains("reported to the community leaders responsible for enforcement at\n[depta@engos.de](mailto:depta@engos.de).\n"));
+}
+
+#[test]
+fn contributor_covenant_2_0() {
+  let dir = package("test_code_of_conduct/package", |manifest| {
+    manifest + "\n[package.metadata.magg.code-of-conduct]\ncode = \"contributor-covenant-2.0\"\ncontact = \"conduct@engos.de\"\n"
+  });
+  let content = code_of_conduct(&dir);
+  assert!(content.contains("version 2.0, available at"));
+  assert!(content.contains("\n[conduct@engos.de](mailto:conduct@engos.de).\n"));
+}
+
+#[test]
+fn contributor_covenant_3_0() {
+  let dir = package("test_code_of_conduct/package", |manifest| {
+    manifest + "\n[package.metadata.magg.code-of-conduct]\ncode = \"contributor-covenant-3.0\"\ncontact = \"https://engos.de/conduct\"\n"
+  });
+  let content = code_of_conduct(&dir);
+  assert!(content.starts_with("# Contributor Covenant 3.0 Code of Conduct\n"));
+  assert!(content.contains("To report a\npossible violation, contact [https://engos.de/conduct](https://engos.de/conduct).\n"));
+}
+
+#[test]
+fn rust_code() {
+  let dir = package("test_code_of_conduct/package", |manifest| {
+    manifest + "\n[package.metadata.magg.code-of-conduct]\ncode = \"rust\"\ncontact = \"the moderation team on Zulip\"\n"
+  });
+  let content = code_of_conduct(&dir);
+  assert!(content.contains("please contact the moderators at\nthe moderation team on Zulip.\n"));
+  assert!(!content.contains("::CONTACT::"));
+}
+
+#[test]
+fn citizen_code() {
+  let dir = package("test_code_of_conduct/package", |manifest| {
+    manifest + "\n[package.metadata.magg.code-of-conduct]\ncode = \"citizen\"\n"
+  });
+  let content = code_of_conduct(&dir);
+  assert!(content.starts_with("# Citizen Code of Conduct\n\n## 1. Purpose\n\nA primary goal of basic is"));
+  assert!(!content.contains("::NAME::"));
+}
+
+#[test]
+fn unknown_code() {
+  let dir = package("test_code_of_conduct/package", |manifest| {
+    manifest + "\n[package.metadata.magg.code-of-conduct]\ncode = \"unknown\"\n"
+  });
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("code-of-conduct")
+    .code(8)
+    .stdout("")
+    .stderr("error: unknown code of conduct: unknown, expected one of: contributor-covenant-2.0, contributor-covenant-2.1, contributor-covenant-3.0, rust, citizen\n")
+    .execute();
+}
+
+#[test]
+fn contact_not_found() {
+  let dir = package("test_code_of_conduct/package", |manifest| manifest.replace(" <depta@engos.de>", ""));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("code-of-conduct")
+    .code(4)
+    .stdout("")
+    .stderr("error: code of conduct contact not found, set package.metadata.magg.code-of-conduct.contact or e-mail address in package.authors in Cargo.toml\n")
+    .execute();
+}
+
+#[test]
+fn readme_badge() {
+  let dir = package("test_code_of_conduct/package", |manifest| {
+    manifest + "\n[package.metadata.magg.code-of-conduct]\ncode = \"rust\"\n"
+  });
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(0)
+    .stdout("created: README.md\n1 created, 0 updated, 0 unchanged\n")
+    .execute();
+  assert!(read(&dir, "README.md").contains("\n[cc-badge]: https://img.shields.io/badge/Code%20of%20Conduct-Rust-4169E1.svg\n"));
+}
@@ -0,0 +1,208 @@
+use super::*;
+
+#[test]
+fn contributing() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("contributing")
+    .code(0)
+    .stdout("created: CONTRIBUTING.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let content = read(&dir, "CONTRIBUTING.md");
+  assert!(content.starts_with("# Contributing to basic\n"));
+  assert!(content.contains("[issue tracker](https://gitlab.com/EngosSoftware/basic/-/issues)"));
+  assert!(content.contains("[repository](https://gitlab.com/EngosSoftware/basic)"));
+  assert!(content.contains("shall be licensed under `MIT OR Apache-2.0`,"));
+}
+
+#[test]
+fn security() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("security")
+    .code(0)
+    .stdout("created: SECURITY.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let content = read(&dir, "SECURITY.md");
+  assert!(content.contains("|---------|-----------|\n| 1.x | :white_check_mark: |\n| < 1.0 | :x: |\n"));
+  assert!(content.contains("Report vulnerabilities privately to [depta@engos.de](mailto:depta@engos.de), including:"));
+}
+
+#[test]
+fn security_configured() {
+  let dir = package("test_community/package", |manifest| {
+    manifest.replace("1.2.3", "0.4.0-rc.1") + "\n[package.metadata.magg.security]\ncontact = \"https://engos.de/security\"\n"
+  });
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("security")
+    .code(0)
+    .stdout("created: SECURITY.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let content = read(&dir, "SECURITY.md");
+  assert!(content.contains("|---------|-----------|\n| 0.4.x | :white_check_mark: |\n| < 0.4 | :x: |\n"));
+  assert!(content.contains("Report vulnerabilities privately to [https://engos.de/security](https://engos.de/security), including:"));
+}
+
+#[test]
+fn security_released_versions() {
+  let dir = fixture("test_community/package");
+  let git = |args: &[&str]| {
+    let status = std::process::Command::new("git")
+      .current_dir(dir.path())
+      .args(["-c", "user.name=Dariusz Depta", "-c", "user.email=depta@engos.de"])
+      .args(args)
+      .status()
+      .unwrap();
+    assert!(status.success());
+  };
+  git(&["init", "-q"]);
+  git(&["add", "-A"]);
+  git(&["commit", "-q", "-m", "release"]);
+  for tag in ["v0.1.0", "v0.1.4", "v0.2.0", "basic-v1.0.0", "v1.1.0", "v2.0.0-rc.1", "other-v5.0.0"] {
+    git(&["tag", tag]);
+  }
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("security")
+    .code(0)
+    .stdout("created: SECURITY.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let content = read(&dir, "SECURITY.md");
+  assert!(content.contains("|---------|-----------|\n| 1.x | :white_check_mark: |\n| 0.2.x | :x: |\n| 0.1.x | :x: |\n\n"));
+}
+
+#[test]
+fn support() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("support")
+    .code(0)
+    .stdout("created: SUPPORT.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, "SUPPORT.md").contains("[API documentation](https://docs.rs/basic)"));
+}
+
+#[test]
+fn issue_templates() {
+  let dir = fixture("test_community/package");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("issue-templates")
+    .code(0)
+    .stdout("created: .gitlab/issue_templates/Bug.md\ncreated: .gitlab/issue_templates/Feature.md\ncreated: .gitlab/merge_request_templates/Default.md\n3 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let bug = read(&dir, ".gitlab/issue_templates/Bug.md");
+  assert!(bug.contains("<!-- Version of basic where the problem occurs, like: 1.2.3 -->\n"));
+  assert!(bug.contains("security policy: https://gitlab.com/EngosSoftware/basic/-/blob/main/SECURITY.md -->\n"));
+  assert!(bug.ends_with("/label ~bug\n"));
+  assert!(read(&dir, ".gitlab/issue_templates/Feature.md").starts_with("<!-- Suggest an idea for basic -->\n"));
+  assert!(read(&dir, ".gitlab/merge_request_templates/Default.md").starts_with("## Description\n"));
+  assert!(!dir.path().join(".github").exists());
+}
+
+#[test]
+fn issue_templates_github() {
+  let dir = package("test_community/package", |manifest| manifest.replace("gitlab.com", "github.com"));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("issue-templates")
+    .code(0)
+    .stdout("created: .github/ISSUE_TEMPLATE/bug_report.yml\ncreated: .github/ISSUE_TEMPLATE/feature_request.yml\ncreated: .github/ISSUE_TEMPLATE/config.yml\ncreated: .github/pull_request_template.md\n4 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, ".github/ISSUE_TEMPLATE/bug_report.yml").contains("      placeholder: \"1.2.3\"\n"));
+  assert!(read(&dir, ".github/ISSUE_TEMPLATE/feature_request.yml").starts_with("name: Feature request\ndescription: Suggest an idea for basic\n"));
+  assert!(read(&dir, ".github/ISSUE_TEMPLATE/config.yml").contains("    url: https://github.com/EngosSoftware/basic/blob/main/SECURITY.md\n"));
+  assert!(read(&dir, ".github/pull_request_template.md").starts_with("## Description\n"));
+}
+
+#[test]
+fn issue_templates_gitea() {
+  let dir = package("test_community/package", |manifest| manifest.replace("gitlab.com", "codeberg.org"));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("issue-templates")
+    .code(0)
+    .stdout("created: .gitea/ISSUE_TEMPLATE/bug_report.yml\ncreated: .gitea/ISSUE_TEMPLATE/feature_request.yml\ncreated: .gitea/ISSUE_TEMPLATE/config.yml\ncreated: .gitea/pull_request_template.md\n4 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, ".gitea/ISSUE_TEMPLATE/config.yml").contains("    url: https://codeberg.org/EngosSoftware/basic/src/branch/main/SECURITY.md\n"));
+}
+
+#[test]
+fn check() {
+  let dir = fixture("test_community/package");
+  let expected = r#"missing file: .gitlab/issue_templates/Bug.md
+missing file: .gitlab/issue_templates/Feature.md
+missing file: .gitlab/merge_request_templates/Default.md
+"#;
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("issue-templates")
+    .arg("--check")
+    .code(1)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("issue-templates")
+    .code(0)
+    .stdout("created: .gitlab/issue_templates/Bug.md\ncreated: .gitlab/issue_templates/Feature.md\ncreated: .gitlab/merge_request_templates/Default.md\n3 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  std::fs::write(dir.path().join(".gitlab/merge_request_templates/Default.md"), "## Changes\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("issue-templates")
+    .arg("--check")
+    .code(1)
+    .stdout("modified file: .gitlab/merge_request_templates/Default.md\n")
+    .stderr("")
+    .execute();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("issue-templates")
+    .code(10)
+    .stdout("not overwritten: .gitlab/merge_request_templates/Default.md\n0 created, 0 updated, 2 unchanged, 1 not overwritten\n")
+    .stderr("error: 1 file was not overwritten because of changed content, use --force to overwrite or --dry-run to display changes\n")
+    .execute();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("issue-templates")
+    .arg("--force")
+    .code(0)
+    .stdout("updated: .gitlab/merge_request_templates/Default.md\n0 created, 1 updated, 2 unchanged\n")
+    .stderr("")
+    .execute();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("issue-templates")
+    .arg("--check")
+    .code(0)
+    .stdout("")
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn contact_not_found() {
+  let dir = package("test_community/package", |manifest| manifest.replace(" <depta@engos.de>", ""));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("security")
+    .code(4)
+    .stdout("")
+    .stderr("error: security contact not found, set package.metadata.magg.security.contact or e-mail address in package.authors in Cargo.toml\n")
+    .execute();
+}
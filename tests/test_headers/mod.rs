@@ -0,0 +1,3 @@
+use super::*;
+
+mod test_spdx_headers;
@@ -0,0 +1,3 @@
+// @generated by a tool
+
+pub fn generated() {}
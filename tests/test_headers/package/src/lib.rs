@@ -0,0 +1,4 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2020-2021 Someone
+
+pub fn lib() {}
@@ -0,0 +1,148 @@
+use super::*;
+
+/// Returns the current year.
+fn year() -> i32 {
+  time::OffsetDateTime::now_utc().year()
+}
+
+#[test]
+fn add_and_update() {
+  let dir = fixture("test_headers/package");
+  std::fs::write(dir.path().join(".gitignore"), "ignored.rs\n").unwrap();
+  std::fs::write(dir.path().join("ignored.rs"), "fn ignored() {}\n").unwrap();
+  let expected = r#"updated: Cargo.toml
+updated: scripts/build.sh
+updated: src/lib.rs
+updated: src/main.rs
+0 created, 4 updated, 0 unchanged
+"#;
+  cli_assert::command!().current_dir(dir.path()).arg("headers").code(0).stdout(expected).stderr("").execute();
+  let year = year();
+  assert_eq!(
+    format!("// SPDX-License-Identifier: MIT OR Apache-2.0\n// Copyright (c) {year} Dariusz Depta\n\nfn main() {{}}\n"),
+    read(&dir, "src/main.rs")
+  );
+  assert_eq!(
+    format!("// SPDX-License-Identifier: MIT OR Apache-2.0\n// Copyright (c) 2020-{year} Dariusz Depta\n\npub fn lib() {{}}\n"),
+    read(&dir, "src/lib.rs")
+  );
+  assert_eq!(
+    format!("#!/usr/bin/env bash\n# SPDX-License-Identifier: MIT OR Apache-2.0\n# Copyright (c) {year} Dariusz Depta\n\necho \"build\"\n"),
+    read(&dir, "scripts/build.sh")
+  );
+  assert_eq!("// @generated by a tool\n\npub fn generated() {}\n", read(&dir, "src/generated.rs"));
+  assert_eq!("fn ignored() {}\n", read(&dir, "ignored.rs"));
+  // Running again does not change anything.
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("headers")
+    .arg("--check")
+    .code(0)
+    .stdout("")
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn check() {
+  let dir = fixture("test_headers/package");
+  let expected = r#"missing header: Cargo.toml
+missing header: scripts/build.sh
+outdated header: src/lib.rs
+missing header: src/main.rs
+"#;
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("headers")
+    .arg("--check")
+    .code(1)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+  assert_eq!("fn main() {}\n", read(&dir, "src/main.rs"));
+}
+
+#[test]
+fn nested_package() {
+  let dir = fixture("test_headers/package");
+  std::fs::create_dir_all(dir.path().join("tests/nested/src")).unwrap();
+  std::fs::write(dir.path().join("tests/nested/Cargo.toml"), "[package]\nname = \"nested\"\nlicense = \"MIT\"\n").unwrap();
+  std::fs::write(dir.path().join("tests/nested/src/lib.rs"), "pub fn nested() {}\n").unwrap();
+  std::fs::write(dir.path().join("tests/test.rs"), "#[test]\nfn test() {}\n").unwrap();
+  let expected = r#"missing header: Cargo.toml
+missing header: scripts/build.sh
+outdated header: src/lib.rs
+missing header: src/main.rs
+missing header: tests/test.rs
+"#;
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("headers")
+    .arg("--check")
+    .code(1)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn dry_run() {
+  let dir = fixture("test_headers/package");
+  let expected = format!(
+    r#"--- src/main.rs
++++ src/main.rs
+@@ -1,1 +1,4 @@
++// SPDX-License-Identifier: MIT OR Apache-2.0
++// Copyright (c) {} Dariusz Depta
++
+ fn main() {{}}
+"#,
+    year()
+  );
+  let mut command = cli_assert::command!().current_dir(dir.path()).arg("headers").arg("--dry-run").code(0).stderr("");
+  command.execute();
+  let output = command.get_stdout();
+  assert!(output.contains(&expected));
+  assert!(output.ends_with("updated: src/main.rs\n0 created, 4 updated, 0 unchanged (dry run)\n"));
+  assert_eq!("fn main() {}\n", read(&dir, "src/main.rs"));
+}
+
+#[test]
+fn inner_attribute() {
+  let dir = fixture("test_headers/package");
+  std::fs::write(dir.path().join("src/no_std.rs"), "#![no_std]\n\npub fn lib() {}\n").unwrap();
+  cli_assert::command!().current_dir(dir.path()).arg("headers").code(0).stderr("").execute();
+  assert_eq!(
+    format!(
+      "// SPDX-License-Identifier: MIT OR Apache-2.0\n// Copyright (c) {} Dariusz Depta\n\n#![no_std]\n\npub fn lib() {{}}\n",
+      year()
+    ),
+    read(&dir, "src/no_std.rs")
+  );
+}
+
+#[test]
+fn line_endings() {
+  let dir = fixture("test_headers/package");
+  let header = format!("// SPDX-License-Identifier: MIT OR Apache-2.0\n// Copyright (c) {} Dariusz Depta\n\n", year());
+  std::fs::write(dir.path().join("src/crlf.rs"), format!("{header}fn crlf() {{}}\n").replace('\n', "\r\n")).unwrap();
+  std::fs::write(dir.path().join("src/unterminated.rs"), format!("{header}fn unterminated() {{}}")).unwrap();
+  std::fs::write(dir.path().join("src/missing.rs"), "fn missing() {}\r\n").unwrap();
+  let expected = r#"missing header: Cargo.toml
+missing header: scripts/build.sh
+outdated header: src/lib.rs
+missing header: src/main.rs
+missing header: src/missing.rs
+"#;
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("headers")
+    .arg("--check")
+    .code(1)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+  cli_assert::command!().current_dir(dir.path()).arg("headers").code(0).stderr("").execute();
+  assert_eq!(format!("{header}fn missing() {{}}\n").replace('\n', "\r\n"), read(&dir, "src/missing.rs"));
+  assert_eq!(format!("{header}fn unterminated() {{}}"), read(&dir, "src/unterminated.rs"));
+}
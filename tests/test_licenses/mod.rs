@@ -0,0 +1,17 @@
+use super::*;
+
+mod test_audit;
+mod test_check;
+mod test_copyright;
+mod test_spdx;
+
+/// Returns sorted names of license files in the directory.
+fn license_files(dir: &tempfile::TempDir) -> Vec<String> {
+  let mut file_names = std::fs::read_dir(dir.path())
+    .unwrap()
+    .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+    .filter(|file_name| file_name.starts_with("LICENSE") || file_name == "NOTICE")
+    .collect::<Vec<String>>();
+  file_names.sort();
+  file_names
+}
@@ -0,0 +1,122 @@
+use super::*;
+
+/// Adds the vendored dependency with the specified manifest and files to the lock file of the package.
+fn vendor(dir: &tempfile::TempDir, name: &str, version: &str, manifest: &str, files: &[(&str, &str)]) {
+  let lock = read(dir, "Cargo.lock") + &format!("\n[[package]]\nname = \"{name}\"\nversion = \"{version}\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n");
+  std::fs::write(dir.path().join("Cargo.lock"), lock).unwrap();
+  let vendor_dir = dir.path().join("vendor").join(format!("{name}-{version}"));
+  std::fs::create_dir_all(&vendor_dir).unwrap();
+  std::fs::write(vendor_dir.join("Cargo.toml"), manifest).unwrap();
+  for (file_name, content) in files {
+    std::fs::write(vendor_dir.join(file_name), content).unwrap();
+  }
+}
+
+#[test]
+fn policy_violation() {
+  let dir = fixture("test_licenses/audit");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("audit")
+    .arg("--allow-missing")
+    .code(1)
+    .stdout("created: THIRD-PARTY-NOTICES\n1 created, 0 updated, 0 unchanged\nlicense not allowed: gamma 0.3.0: GPL-3.0-only\n")
+    .stderr("warning: unresolved dependency: delta 9.9.9: sources not found locally, run `cargo fetch` or `cargo vendor`\n")
+    .execute();
+  let notices = read(&dir, "THIRD-PARTY-NOTICES");
+  assert!(notices.starts_with("THIRD-PARTY NOTICES\n\napp uses the following third-party software, grouped by license.\n"));
+  assert!(notices.contains("\nApache-2.0\n================================================================================\n\nUsed by:\n\n- alpha 1.0.0\n\nApache License\n"));
+  assert!(
+    notices
+      .contains("\nGPL-3.0-only\n================================================================================\n\nUsed by:\n\n- gamma 0.3.0\n\nGNU GENERAL PUBLIC LICENSE\n")
+  );
+  assert!(
+    notices.contains(
+      "\nMIT\n================================================================================\n\nUsed by:\n\n- beta 0.2.1\n\nMIT License\n\nCopyright (c) Beta Author\n"
+    )
+  );
+}
+
+#[test]
+fn without_policy() {
+  let dir = package("test_licenses/audit", |manifest| manifest.replace("allowed = [\"MIT\", \"Apache-2.0\"]\n", ""));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("audit")
+    .arg("--allow-missing")
+    .code(0)
+    .stdout("created: THIRD-PARTY-NOTICES\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("warning: unresolved dependency: delta 9.9.9: sources not found locally, run `cargo fetch` or `cargo vendor`\n")
+    .execute();
+  // The first alternative of the license expression is chosen.
+  let notices = read(&dir, "THIRD-PARTY-NOTICES");
+  assert!(notices.contains("\nApache-2.0\n================================================================================\n\nUsed by:\n\n- alpha 1.0.0\n"));
+  assert!(notices.contains("\nUsed by:\n\n- beta 0.2.1\n"));
+}
+
+#[test]
+fn unresolved_dependency() {
+  let dir = package("test_licenses/audit", |manifest| manifest.replace("allowed = [\"MIT\", \"Apache-2.0\"]\n", ""));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("audit")
+    .code(1)
+    .stdout("unresolved dependency: delta 9.9.9: sources not found locally, run `cargo fetch` or `cargo vendor`\n")
+    .stderr("error: THIRD-PARTY-NOTICES not generated because licenses of some dependencies could not be resolved, use --allow-missing to generate incomplete notices\n")
+    .execute();
+  assert!(!dir.path().join("THIRD-PARTY-NOTICES").exists());
+}
+
+#[test]
+fn invalid_dependency_manifest() {
+  let dir = package("test_licenses/audit", |manifest| manifest.replace("allowed = [\"MIT\", \"Apache-2.0\"]\n", ""));
+  vendor(&dir, "epsilon", "0.5.0", "[package\n", &[]);
+  let mut command = cli_assert::command!().current_dir(dir.path()).arg("licenses").arg("audit").code(1);
+  command.execute();
+  let stdout = command.get_stdout();
+  assert!(stdout.starts_with("unresolved dependency: delta 9.9.9: sources not found locally"));
+  assert!(stdout.contains("\nunresolved dependency: epsilon 0.5.0: failed to parse file "));
+}
+
+#[test]
+fn license_file() {
+  let dir = fixture("test_licenses/audit");
+  vendor(
+    &dir,
+    "epsilon",
+    "0.5.0",
+    "[package]\nname = \"epsilon\"\nversion = \"0.5.0\"\nlicense-file = \"LICENSE.txt\"\n",
+    &[("LICENSE.txt", "Epsilon License\n")],
+  );
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("audit")
+    .arg("--allow-missing")
+    .code(1)
+    .stdout(
+      "created: THIRD-PARTY-NOTICES\n1 created, 0 updated, 0 unchanged\nlicense not allowed: gamma 0.3.0: GPL-3.0-only\nlicense not allowed: epsilon 0.5.0: LicenseRef-epsilon\n",
+    )
+    .stderr("warning: unresolved dependency: delta 9.9.9: sources not found locally, run `cargo fetch` or `cargo vendor`\n")
+    .execute();
+  assert!(
+    read(&dir, "THIRD-PARTY-NOTICES")
+      .contains("\nLicenseRef-epsilon\n================================================================================\n\nUsed by:\n\n- epsilon 0.5.0\n\nEpsilon License\n")
+  );
+}
+
+#[test]
+fn lock_file_not_found() {
+  let dir = package("test_licenses/package", |manifest| manifest.replace("MIT OR Apache-2.0", "MIT"));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("audit")
+    .code(4)
+    .stdout("")
+    .stderr("error: Cargo.lock not found, run `cargo generate-lockfile`\n")
+    .execute();
+}
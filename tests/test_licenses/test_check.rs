@@ -0,0 +1,91 @@
+use super::*;
+
+/// Generates license files in the directory.
+fn generate(dir: &tempfile::TempDir) {
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(0)
+    .stdout("created: LICENSE-MIT\ncreated: LICENSE\ncreated: NOTICE\n3 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+}
+
+/// Writes the file in the directory.
+fn write(dir: &tempfile::TempDir, file_name: &str, content: &str) {
+  std::fs::write(dir.path().join(file_name), content).unwrap();
+}
+
+#[test]
+fn up_to_date() {
+  let dir = fixture("test_licenses/package");
+  generate(&dir);
+  // Changes in whitespace and copyright lines are ignored.
+  let mit = read(&dir, "LICENSE-MIT")
+    .replace("Copyright (c) ", "Copyright (c) 2001-2002 Other Author, ")
+    .replace("\n\n", "\n  \n\n")
+    .replace("deal\n", "deal   \n");
+  write(&dir, "LICENSE-MIT", &mit);
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("--check")
+    .code(0)
+    .stdout("")
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn missing() {
+  let dir = fixture("test_licenses/package");
+  let expected = r#"missing license file: LICENSE-MIT
+missing license file: LICENSE
+missing license file: NOTICE
+"#;
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("--check")
+    .code(1)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+  assert!(license_files(&dir).is_empty());
+}
+
+#[test]
+fn modified() {
+  let dir = fixture("test_licenses/package");
+  generate(&dir);
+  let mit = read(&dir, "LICENSE-MIT").replace("free of charge", "for a small fee");
+  write(&dir, "LICENSE-MIT", &mit);
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("--check")
+    .code(1)
+    .stdout("modified license file: LICENSE-MIT\n")
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn license_changed() {
+  let dir = fixture("test_licenses/package");
+  generate(&dir);
+  let manifest = read(&dir, "Cargo.toml").replace("MIT OR Apache-2.0", "MIT");
+  write(&dir, "Cargo.toml", &manifest);
+  let expected = r#"modified license file: LICENSE
+extra license file: LICENSE-MIT
+extra license file: NOTICE
+"#;
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("--check")
+    .code(1)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+}
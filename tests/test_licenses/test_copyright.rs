@@ -0,0 +1,106 @@
+use super::*;
+
+/// Commits a change in the directory as the specified author, at the specified year.
+fn commit(dir: &tempfile::TempDir, author: &str, year: i32) {
+  let changes = std::fs::read_to_string(dir.path().join("CHANGES")).unwrap_or_default();
+  std::fs::write(dir.path().join("CHANGES"), format!("{changes}{author} {year}\n")).unwrap();
+  let date = format!("{year}-06-15T12:00:00+00:00");
+  let email = format!("{}@example.com", author.to_lowercase().replace(' ', "."));
+  for args in [vec!["add", "-A"], vec!["commit", "-q", "-m", "commit"]] {
+    let status = std::process::Command::new("git")
+      .current_dir(dir.path())
+      .args(args)
+      .env("GIT_AUTHOR_NAME", author)
+      .env("GIT_AUTHOR_EMAIL", &email)
+      .env("GIT_AUTHOR_DATE", &date)
+      .env("GIT_COMMITTER_NAME", author)
+      .env("GIT_COMMITTER_EMAIL", &email)
+      .env("GIT_COMMITTER_DATE", &date)
+      .status()
+      .unwrap();
+    assert!(status.success());
+  }
+}
+
+/// Returns the package with MIT license under version control.
+fn repository() -> tempfile::TempDir {
+  let dir = package("test_licenses/package", |manifest| manifest.replace("MIT OR Apache-2.0", "MIT"));
+  let status = std::process::Command::new("git").current_dir(dir.path()).args(["init", "-q"]).status().unwrap();
+  assert!(status.success());
+  dir
+}
+
+/// Replaces the content of the manifest.
+fn replace_manifest(dir: &tempfile::TempDir, from: &str, to: &str) {
+  let manifest = read(dir, "Cargo.toml").replace(from, to);
+  std::fs::write(dir.path().join("Cargo.toml"), manifest).unwrap();
+}
+
+#[test]
+fn years_from_git_history() {
+  let dir = repository();
+  commit(&dir, "Dariusz Depta", 2018);
+  commit(&dir, "Dariusz Depta", 2021);
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(0)
+    .stdout("created: LICENSE\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, "LICENSE").contains("\nCopyright (c) 2018-2021 Dariusz Depta\n"));
+}
+
+#[test]
+fn holders_from_git_authors() {
+  let dir = repository();
+  replace_manifest(&dir, "authors = [\"Dariusz Depta <depta@engos.de>\"]\n", "");
+  for year in 2015..2024 {
+    commit(&dir, "Dariusz Depta", year);
+  }
+  commit(&dir, "Jan Kowalski", 2024);
+  commit(&dir, "Jan Kowalski", 2024);
+  commit(&dir, "Occasional Contributor", 2025);
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(0)
+    .stdout("created: LICENSE\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, "LICENSE").contains("\nCopyright (c) 2015-2025 Dariusz Depta, Jan Kowalski\n"));
+}
+
+#[test]
+fn copyright_from_metadata() {
+  let dir = repository();
+  commit(&dir, "Dariusz Depta", 2021);
+  let metadata = r#"
+[package.metadata.magg.copyright]
+start-year = 2015
+end-year = "2026"
+holders = ["Dariusz Depta", "Engos Software"]
+"#;
+  std::fs::write(dir.path().join("Cargo.toml"), read(&dir, "Cargo.toml") + metadata).unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(0)
+    .stdout("created: LICENSE\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, "LICENSE").contains("\nCopyright (c) 2015-2026 Dariusz Depta, Engos Software\n"));
+}
+
+#[test]
+fn holders_not_found() {
+  let dir = package("test_licenses/package", |manifest| manifest.replace("MIT OR Apache-2.0", "MIT"));
+  replace_manifest(&dir, "authors = [\"Dariusz Depta <depta@engos.de>\"]\n", "");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(4)
+    .stdout("")
+    .stderr("error: copyright holders not found, set package.authors or package.metadata.magg.copyright.holders in Cargo.toml\n")
+    .execute();
+}
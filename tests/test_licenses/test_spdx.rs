@@ -0,0 +1,96 @@
+use super::*;
+
+#[test]
+fn mit_or_apache() {
+  let dir = fixture("test_licenses/package");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(0)
+    .stdout("created: LICENSE-MIT\ncreated: LICENSE\ncreated: NOTICE\n3 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert_eq!(vec!["LICENSE", "LICENSE-MIT", "NOTICE"], license_files(&dir));
+  assert!(read(&dir, "LICENSE").trim_start().starts_with("Apache License"));
+  assert!(read(&dir, "LICENSE-MIT").starts_with("MIT License\n"));
+}
+
+#[test]
+fn apache_with_llvm_exception() {
+  let dir = package("test_licenses/package", |manifest| manifest.replace("MIT OR Apache-2.0", "Apache-2.0 WITH LLVM-exception"));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(0)
+    .stdout("created: LICENSE\ncreated: NOTICE\n2 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert_eq!(vec!["LICENSE", "NOTICE"], license_files(&dir));
+  assert!(read(&dir, "LICENSE").contains("---- LLVM Exceptions to the Apache 2.0 License ----"));
+}
+
+#[test]
+fn single_license() {
+  let dir = package("test_licenses/package", |manifest| manifest.replace("MIT OR Apache-2.0", "MPL-2.0"));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(0)
+    .stdout("created: LICENSE\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert_eq!(vec!["LICENSE"], license_files(&dir));
+  assert!(read(&dir, "LICENSE").starts_with("Mozilla Public License Version 2.0\n"));
+}
+
+#[test]
+fn only_suffix() {
+  let dir = package("test_licenses/package", |manifest| manifest.replace("MIT OR Apache-2.0", "GPL-3.0-only"));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(0)
+    .stdout("created: LICENSE\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert_eq!(vec!["LICENSE"], license_files(&dir));
+  assert!(read(&dir, "LICENSE").contains("GNU GENERAL PUBLIC LICENSE\n"));
+}
+
+#[test]
+fn compound_expression() {
+  let dir = package("test_licenses/package", |manifest| manifest.replace("MIT OR Apache-2.0", "(BSD-3-Clause OR ISC) AND Zlib"));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(0)
+    .stdout("created: LICENSE-BSD-3-Clause\ncreated: LICENSE-ISC\ncreated: LICENSE-Zlib\n3 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert_eq!(vec!["LICENSE-BSD-3-Clause", "LICENSE-ISC", "LICENSE-Zlib"], license_files(&dir));
+}
+
+#[test]
+fn unknown_identifier() {
+  let dir = package("test_licenses/package", |manifest| manifest.replace("MIT OR Apache-2.0", "MIT OR Foo-1.0"));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(8)
+    .stdout("")
+    .stderr("error: unknown license identifier: Foo-1.0\n")
+    .execute();
+  assert!(license_files(&dir).is_empty());
+}
+
+#[test]
+fn invalid_expression() {
+  let dir = package("test_licenses/package", |manifest| manifest.replace("MIT OR Apache-2.0", "MIT OR"));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .code(7)
+    .stdout("")
+    .stderr("error: unexpected end of license expression: MIT OR\n")
+    .execute();
+}
@@ -0,0 +1,4 @@
+//! Invalid package.
+
+/// Included data.
+pub const DATA: &str = include_str!("data/DATA");
@@ -0,0 +1,4 @@
+//! Valid package.
+
+/// Included text.
+pub const TEXT: &str = include_str!("TEXT");
@@ -0,0 +1,4 @@
+use super::*;
+
+mod test_manifest;
+mod test_markdown;
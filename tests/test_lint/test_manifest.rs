@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn invalid_manifest() {
+  let expected = r#"error: Cargo.toml:1: package.description is not set, it is required by crates.io
+  help: add a short description, like: description = "Sophisticated tooling for Rust developers"
+error: Cargo.toml:5: package.license is not a valid SPDX expression: unexpected end of license expression: MIT OR
+  help: use SPDX license identifiers, like: license = "MIT OR Apache-2.0"
+error: Cargo.toml:7: README file 'MISSING.md' does not exist
+  help: create the file with `magg readme` or correct package.readme
+error: Cargo.toml:8: package.keywords has 6 entries, crates.io accepts at most 5
+  help: remove 1 of the keywords
+error: Cargo.toml:8: invalid keyword 'crates.io'
+  help: keywords must start with a letter or digit, contain only letters, digits, '_', '-' or '+', and have at most 20 characters
+error: Cargo.toml:9: invalid category 'cli'
+  help: use one of the slugs listed at https://crates.io/category_slugs
+error: Cargo.toml:9: invalid category 'command-line-utility'
+  help: did you mean 'command-line-utilities'? see https://crates.io/category_slugs
+error: Cargo.toml:9: invalid category 'testing'
+  help: did you mean 'development-tools::testing'? see https://crates.io/category_slugs
+error: Cargo.toml:10: package.rust-version '1.85-beta' is not a valid Rust version
+  help: use the version without pre-release suffix, like: rust-version = "1.85"
+warning: Cargo.toml:11: development file 'Taskfile.yml' will be published
+  help: add 'Taskfile.yml' to package.exclude
+error: Cargo.toml:11: file 'src/data/DATA' included in src/lib.rs:4 will not be published
+  help: remove 'src/data/' from package.exclude
+"#;
+  cli_assert::command!()
+    .current_dir("tests/test_lint/manifest/invalid")
+    .arg("lint")
+    .arg("manifest")
+    .code(1)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn valid_manifest() {
+  cli_assert::command!()
+    .current_dir("tests/test_lint/manifest/valid")
+    .arg("lint")
+    .arg("manifest")
+    .code(0)
+    .stdout("")
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn warnings_only() {
+  let dir = package("test_lint/manifest/valid", |manifest| {
+    manifest.replace("keywords = [\"lint\", \"manifest\"]\n", "").replace("\"Taskfile.yml\", ", "")
+  });
+  let expected = r#"warning: Cargo.toml:1: package.keywords is not set, the crate will be harder to find on crates.io
+  help: add up to 5 keywords
+warning: Cargo.toml:11: development file 'Taskfile.yml' will be published
+  help: add 'Taskfile.yml' to package.exclude
+"#;
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("lint")
+    .arg("manifest")
+    .code(0)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+}
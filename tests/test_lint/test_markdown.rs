@@ -0,0 +1,32 @@
+#[test]
+fn broken_references_and_links() {
+  let expected = r#"broken.md:4: reference 'guide' is used but not defined
+broken.md:4: linked file 'NOTICE.md' does not exist
+broken.md:13: reference 'unused' is defined but not used
+broken.md:14: reference 'Crates-URL' is already defined in line 12
+broken.md:15: linked file 'CODE_OF_CONDUCT.md' does not exist
+"#;
+  cli_assert::command!()
+    .current_dir("tests/test_lint/markdown")
+    .arg("lint")
+    .arg("markdown")
+    .arg("broken.md")
+    .arg("valid.md")
+    .code(1)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+}
+
+#[test]
+fn valid_references_and_links() {
+  cli_assert::command!()
+    .current_dir("tests/test_lint/markdown")
+    .arg("lint")
+    .arg("markdown")
+    .arg("valid.md")
+    .code(0)
+    .stdout("")
+    .stderr("")
+    .execute();
+}
@@ -0,0 +1,4 @@
+/// Returns the sum of two numbers.
+pub fn add(a: u64, b: u64) -> u64 {
+  a + b
+}
@@ -0,0 +1,7 @@
+//! Basic example.
+
+fn main() {
+  // ANCHOR: greeting
+  println!("Hello!");
+  // ANCHOR_END: greeting
+}
@@ -0,0 +1,7 @@
+use super::*;
+
+mod test_doctest;
+mod test_includes;
+mod test_languages;
+mod test_licenses;
+mod test_repository;
@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn passing_example() {
+  let dir = fixture("test_readme/doctest");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--test-examples")
+    .code(0)
+    .execute();
+  assert!(!dir.path().join("README.md").exists());
+}
+
+#[test]
+fn failing_example() {
+  let dir = fixture("test_readme/doctest");
+  std::fs::write(dir.path().join("docs/README.md"), "## Example\n\n```rust\nassert_eq!(5, adder::add(2, 2));\n```\n").unwrap();
+  let mut command = cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--test-examples")
+    .code(8);
+  command.execute();
+  assert!(command.get_stderr().contains("error: README.md examples failed to compile or run, status "));
+}
+
+#[test]
+fn unclosed_code_block() {
+  let dir = fixture("test_readme/doctest");
+  std::fs::write(dir.path().join("docs/README.md"), "## Example\n\n```rust\nassert_eq!(5, adder::add(2, 3));\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--test-examples")
+    .code(7)
+    .stdout("")
+    .stderr("error: unclosed code block in README.md, line 35\n")
+    .execute();
+}
+
+#[test]
+fn package_edition() {
+  let dir = fixture("test_readme/doctest");
+  let manifest = read(&dir, "Cargo.toml").replace("edition = \"2024\"", "edition = \"2021\"");
+  std::fs::write(dir.path().join("Cargo.toml"), manifest).unwrap();
+  // `gen` is a reserved keyword since edition 2024.
+  std::fs::write(
+    dir.path().join("docs/README.md"),
+    "## Example\n\n```rust\nlet gen = adder::add(2, 2);\nassert_eq!(4, gen);\n```\n",
+  )
+  .unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--test-examples")
+    .code(0)
+    .execute();
+}
@@ -0,0 +1,108 @@
+use super::*;
+
+#[test]
+fn line_range_and_anchor() {
+  let dir = fixture("test_readme/includes");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(0)
+    .stdout("created: README.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let readme = read(&dir, "README.md");
+  let expected = r#"## Example
+
+```rust
+fn main() {
+  // ANCHOR: greeting
+  println!("Hello!");
+  // ANCHOR_END: greeting
+}
+```
+
+## Greeting
+
+```rust
+  println!("Hello!");
+```
+"#;
+  assert!(readme.contains(expected), "{readme}");
+}
+
+#[test]
+fn missing_file() {
+  let dir = fixture("test_readme/includes");
+  std::fs::write(dir.path().join("docs/README.md"), "{{#include examples/missing.rs}}\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(3)
+    .stdout("")
+    .stderr("error: failed to read file: examples/missing.rs, reason: No such file or directory (os error 2)\n")
+    .execute();
+}
+
+#[test]
+fn coverage_badge_kept() {
+  let dir = fixture("test_readme/includes");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(0)
+    .stderr("")
+    .execute();
+  let covered = read(&dir, "README.md").replace("coverage-0%25-21b577", "coverage-87%25-21b577");
+  std::fs::write(dir.path().join("README.md"), &covered).unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(0)
+    .stdout("0 created, 0 updated, 1 unchanged\n")
+    .stderr("")
+    .execute();
+  std::fs::write(dir.path().join("docs/README.md"), "## Changed\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(0)
+    .stdout("updated: README.md\n0 created, 1 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, "README.md").contains("[cov-badge]: https://img.shields.io/badge/coverage-87%25-21b577.svg\n"));
+}
+
+#[test]
+fn coverage_badge_kept_in_output_dir() {
+  let dir = fixture("test_readme/includes");
+  let output_dir = tempfile::TempDir::new().unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--output-dir")
+    .arg(output_dir.path())
+    .code(0)
+    .stderr("")
+    .execute();
+  let covered = read(&output_dir, "README.md").replace("coverage-0%25-21b577", "coverage-87%25-21b577");
+  std::fs::write(output_dir.path().join("README.md"), &covered).unwrap();
+  std::fs::write(dir.path().join("docs/README.md"), "## Changed\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--output-dir")
+    .arg(output_dir.path())
+    .code(0)
+    .stdout(format!("updated: {}\n0 created, 1 updated, 0 unchanged\n", output_dir.path().join("README.md").display()))
+    .stderr("")
+    .execute();
+  assert!(read(&output_dir, "README.md").contains("[cov-badge]: https://img.shields.io/badge/coverage-87%25-21b577.svg\n"));
+  assert!(!dir.path().join("README.md").exists());
+}
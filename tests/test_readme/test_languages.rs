@@ -0,0 +1,86 @@
+use super::*;
+
+#[test]
+fn english_and_german() {
+  let dir = fixture("test_readme/includes");
+  std::fs::write(dir.path().join("docs/README.de.md"), "## Beispiel\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("docs/README.de.md")
+    .code(0)
+    .stdout("created: README.md\ncreated: README.de.md\n2 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let english = read(&dir, "README.md");
+  assert!(english.starts_with("### basic\n\n**English** | [Deutsch](README.de.md)\n\n[![crates.io]"));
+  assert!(english.contains("\n## License\n"));
+  assert!(english.contains("\n## Contribution\n\nAny contributions to [basic][repository-url] are greatly appreciated.\n"));
+  let german = read(&dir, "README.de.md");
+  assert!(german.starts_with("### basic\n\n[English](README.md) | **Deutsch**\n\n[![crates.io]"));
+  assert!(german.contains("\n## Beispiel\n"));
+  assert!(german.contains("\n## Lizenz\n"));
+  assert!(german.contains("\n## Mitwirkung\n\nAlle Beiträge zu [basic][repository-url] sind herzlich willkommen.\n"));
+}
+
+#[test]
+fn overridden_translation() {
+  let dir = fixture("test_readme/includes");
+  std::fs::create_dir_all(dir.path().join("docs/i18n")).unwrap();
+  let translation = r###"
+language = "Français"
+license = "## Licence"
+license_one = "Sous [LICENSE]."
+license_any = "Au choix :\n\n[LICENSES]"
+license_all = "Toutes :\n\n[LICENSES]"
+license_expression = "Expression `[EXPRESSION]` :\n\n[LICENSES]"
+license_see = "voir"
+license_and = "et"
+contribution = "## Contribution\n\n[[PACKAGE_NAME]][repository-url]\n"
+"###;
+  std::fs::write(dir.path().join("docs/i18n/fr.toml"), translation).unwrap();
+  std::fs::write(dir.path().join("docs/README.fr.md"), "## Exemple\n").unwrap();
+  let manifest = read(&dir, "Cargo.toml") + "\n[package.metadata.magg.readme]\ntranslations = \"docs/i18n\"\n";
+  std::fs::write(dir.path().join("Cargo.toml"), manifest).unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("docs/README.fr.md")
+    .code(0)
+    .stdout("created: README.md\ncreated: README.fr.md\n2 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let french = read(&dir, "README.fr.md");
+  assert!(french.starts_with("### basic\n\n[English](README.md) | **Français**\n\n"));
+  let expected = r#"## Exemple
+
+## Licence
+
+Au choix :
+
+- [MIT license][mit-url] (voir [LICENSE-MIT][mit-license-url])
+- [Apache License, Version 2.0][apache-url] (voir [LICENSE][apache-license-url] et [NOTICE][apache-notice-url])
+
+## Contribution
+
+[basic][repository-url]
+"#;
+  assert!(french.ends_with(expected));
+}
+
+#[test]
+fn missing_translation() {
+  let dir = fixture("test_readme/includes");
+  std::fs::write(dir.path().join("docs/README.fr.md"), "## Exemple\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("docs/README.fr.md")
+    .code(8)
+    .stdout("")
+    .stderr("error: translation key 'language' not found for language: fr\n")
+    .execute();
+}
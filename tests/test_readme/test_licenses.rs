@@ -0,0 +1,85 @@
+use super::*;
+
+/// Generates README.md files for the package with the specified license and returns their contents.
+fn readmes(license: &str) -> (String, String) {
+  let dir = package("test_readme/includes", |manifest| manifest.replace("MIT OR Apache-2.0", license));
+  std::fs::write(dir.path().join("docs/README.de.md"), "## Beispiel\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("docs/README.de.md")
+    .code(0)
+    .stderr("")
+    .execute();
+  (read(&dir, "README.md"), read(&dir, "README.de.md"))
+}
+
+#[test]
+fn single_license() {
+  let (english, _) = readmes("MPL-2.0");
+  assert!(english.contains("[![mpl-2.0-license][mpl-2.0-badge]][mpl-2.0-license-url]\n[![cc]"));
+  let expected = r#"[mpl-2.0-badge]: https://img.shields.io/badge/License-MPL--2.0-4169E1.svg
+[mpl-2.0-url]: https://spdx.org/licenses/MPL-2.0.html
+[mpl-2.0-license-url]: https://github.com/EngosSoftware/basic/blob/main/LICENSE
+"#;
+  assert!(english.contains(expected));
+  assert!(english.contains("\n## License\n\nLicensed under the [Mozilla Public License 2.0][mpl-2.0-url] (see [LICENSE][mpl-2.0-license-url]).\n\n"));
+  assert!(!english.contains("[mit-"));
+  assert!(!english.contains("[apache-"));
+}
+
+#[test]
+fn all_licenses() {
+  let (english, _) = readmes("MIT AND Apache-2.0 WITH LLVM-exception");
+  let expected = r#"## License
+
+Licensed under all of
+
+- [MIT license][mit-url] (see [LICENSE-MIT][mit-license-url])
+- [Apache License, Version 2.0 with LLVM-exception][apache-llvm-exception-url] (see [LICENSE][apache-llvm-exception-license-url] and [NOTICE][apache-llvm-exception-notice-url])
+
+"#;
+  assert!(english.contains(expected));
+  assert!(english.contains("[apache-llvm-exception-badge]: https://img.shields.io/badge/License-Apache--2.0%20WITH%20LLVM--exception-4169E1.svg\n"));
+}
+
+#[test]
+fn license_expression() {
+  let (english, _) = readmes("(MIT OR Apache-2.0) AND BSD-3-Clause");
+  let expected = r#"Licensed under the license expression `(MIT OR Apache-2.0) AND BSD-3-Clause` combining
+
+- [MIT license][mit-url] (see [LICENSE-MIT][mit-license-url])
+- [Apache License, Version 2.0][apache-url] (see [LICENSE][apache-license-url] and [NOTICE][apache-notice-url])
+- [BSD 3-Clause License][bsd-3-clause-url] (see [LICENSE-BSD-3-Clause][bsd-3-clause-license-url])
+"#;
+  assert!(english.contains(expected));
+}
+
+#[test]
+fn german() {
+  let (_, german) = readmes("MIT OR Apache-2.0");
+  let expected = r#"## Lizenz
+
+Lizenziert nach Ihrer Wahl unter einer der folgenden Lizenzen:
+
+- [MIT license][mit-url] (siehe [LICENSE-MIT][mit-license-url])
+- [Apache License, Version 2.0][apache-url] (siehe [LICENSE][apache-license-url] und [NOTICE][apache-notice-url])
+
+## Mitwirkung
+"#;
+  assert!(german.contains(expected));
+}
+
+#[test]
+fn missing_license() {
+  let dir = package("test_readme/includes", |manifest| manifest.replace("license = \"MIT OR Apache-2.0\"\n", ""));
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(4)
+    .stdout("")
+    .stderr("error: package.license not found in Cargo.toml\n")
+    .execute();
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+/// Generates README.md for the dual-licensed package with specified manifest and returns the link definitions.
+fn links(manifest: &str) -> String {
+  let dir = fixture("test_readme/includes");
+  std::fs::write(dir.path().join("Cargo.toml"), format!("{manifest}license = \"MIT OR Apache-2.0\"\n")).unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(0)
+    .stdout("created: README.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  read(&dir, "README.md")
+    .lines()
+    .filter(|line| line.starts_with("[build-badge-linux]:") || line.starts_with("[mit-license-url]:") || line.starts_with("[repository-url]:"))
+    .collect::<Vec<&str>>()
+    .join("\n")
+}
+
+#[test]
+fn https_without_git_suffix() {
+  let expected = r#"[build-badge-linux]: https://github.com/EngosSoftware/basic/actions/workflows/build-linux.yml/badge.svg
+[mit-license-url]: https://github.com/EngosSoftware/basic/blob/main/LICENSE-MIT
+[repository-url]: https://github.com/EngosSoftware/basic"#;
+  assert_eq!(expected, links("[package]\nname = \"basic\"\nrepository = \"https://github.com/EngosSoftware/basic\"\n"));
+}
+
+#[test]
+fn ssh() {
+  let expected = r#"[build-badge-linux]: https://github.com/EngosSoftware/basic/actions/workflows/build-linux.yml/badge.svg
+[mit-license-url]: https://github.com/EngosSoftware/basic/blob/main/LICENSE-MIT
+[repository-url]: https://github.com/EngosSoftware/basic"#;
+  assert_eq!(expected, links("[package]\nname = \"basic\"\nrepository = \"git@github.com:EngosSoftware/basic.git\"\n"));
+  assert_eq!(
+    expected,
+    links("[package]\nname = \"basic\"\nrepository = \"ssh://git@github.com/EngosSoftware/basic.git\"\n")
+  );
+}
+
+#[test]
+fn gitlab() {
+  let expected = r#"[build-badge-linux]: https://gitlab.com/engos/group/basic/badges/main/pipeline.svg
+[mit-license-url]: https://gitlab.com/engos/group/basic/-/blob/main/LICENSE-MIT
+[repository-url]: https://gitlab.com/engos/group/basic"#;
+  assert_eq!(expected, links("[package]\nname = \"basic\"\nrepository = \"https://gitlab.com/engos/group/basic.git\"\n"));
+}
+
+#[test]
+fn workspace_inheritance() {
+  let expected = r#"[build-badge-linux]: https://codeberg.org/engos/basic/actions/workflows/build-linux.yml/badge.svg
+[mit-license-url]: https://codeberg.org/engos/basic/src/branch/main/LICENSE-MIT
+[repository-url]: https://codeberg.org/engos/basic"#;
+  let manifest = r#"[workspace]
+
+[workspace.package]
+repository = "https://codeberg.org/engos/basic.git"
+
+[package]
+name = "basic"
+repository.workspace = true
+"#;
+  assert_eq!(expected, links(manifest));
+}
+
+#[test]
+fn invalid_url() {
+  let dir = fixture("test_readme/includes");
+  std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"basic\"\nrepository = \"github.com\"\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(8)
+    .stdout("")
+    .stderr("error: invalid repository URL: github.com\n")
+    .execute();
+}
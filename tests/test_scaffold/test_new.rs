@@ -0,0 +1,217 @@
+const AUTHOR: &str = "John Doe <john@example.com>";
+
+const REPOSITORY: &str = "https://github.com/john/demo";
+
+/// Summary of files written when scaffolding the binary.
+const BINARY_FILES: &str = r#"created: demo/Cargo.toml
+created: demo/.gitignore
+created: demo/rustfmt.toml
+created: demo/Taskfile.yml
+created: demo/docs/README.md
+created: demo/manual/book.toml
+created: demo/manual/src/SUMMARY.md
+created: demo/manual/src/README.md
+created: demo/src/main.rs
+created: demo/tests/mod.rs
+created: demo/tests/test_cli/mod.rs
+created: demo/tests/test_cli/test_main.rs
+created: demo/README.md
+created: demo/LICENSE-MIT
+created: demo/LICENSE
+created: demo/NOTICE
+created: demo/CODE_OF_CONDUCT.md
+17 created, 0 updated, 0 unchanged
+"#;
+
+/// Summary of files written when scaffolding the library.
+const LIBRARY_FILES: &str = r#"created: ./Cargo.toml
+created: ./.gitignore
+created: ./rustfmt.toml
+created: ./Taskfile.yml
+created: ./docs/README.md
+created: ./manual/book.toml
+created: ./manual/src/SUMMARY.md
+created: ./manual/src/README.md
+created: ./src/lib.rs
+created: ./README.md
+created: ./LICENSE
+created: ./CODE_OF_CONDUCT.md
+12 created, 0 updated, 0 unchanged
+"#;
+
+#[test]
+fn binary() {
+  let dir = tempfile::TempDir::new().unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("new")
+    .arg("demo")
+    .arg("--author")
+    .arg(AUTHOR)
+    .arg("--description")
+    .arg("Demo application")
+    .arg("--repository")
+    .arg(REPOSITORY)
+    .code(0)
+    .stdout(BINARY_FILES)
+    .stderr("")
+    .execute();
+  let manifest = std::fs::read_to_string(dir.path().join("demo/Cargo.toml")).unwrap();
+  assert!(manifest.starts_with("[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2024\"\nauthors = [\"John Doe <john@example.com>\"]\n"));
+  assert!(manifest.contains("description = \"Demo application\"\nlicense = \"MIT OR Apache-2.0\"\nrepository = \"https://github.com/john/demo\"\n"));
+  assert!(manifest.ends_with("[dev-dependencies]\ncli-assert = \"0.1\"\n"));
+  let dir = dir.path().join("demo");
+  for file_name in [
+    ".gitignore",
+    "rustfmt.toml",
+    "docs/README.md",
+    "manual/book.toml",
+    "manual/src/SUMMARY.md",
+    "manual/src/README.md",
+    "src/main.rs",
+    "tests/mod.rs",
+    "tests/test_cli/mod.rs",
+    "tests/test_cli/test_main.rs",
+    "LICENSE",
+    "LICENSE-MIT",
+  ] {
+    assert!(dir.join(file_name).exists(), "missing {file_name}");
+  }
+  assert!(!dir.join("src/lib.rs").exists());
+  let read = |file_name: &str| std::fs::read_to_string(dir.join(file_name)).unwrap();
+  assert!(read("Taskfile.yml").contains("\n  install:\n"));
+  assert!(read("README.md").starts_with("### demo\n"));
+  assert!(read("README.md").contains("Demo application"));
+  assert!(read("CODE_OF_CONDUCT.md").contains("[john@example.com](mailto:john@example.com)"));
+  assert!(read("manual/book.toml").contains("authors = [\"John Doe <john@example.com>\"]\n"));
+}
+
+#[test]
+fn library() {
+  let dir = tempfile::TempDir::new().unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("init")
+    .arg("--name")
+    .arg("demo-lib")
+    .arg("--lib")
+    .arg("--license")
+    .arg("MIT")
+    .arg("--author")
+    .arg(AUTHOR)
+    .arg("--repository")
+    .arg(REPOSITORY)
+    .code(0)
+    .stdout(LIBRARY_FILES)
+    .stderr("")
+    .execute();
+  let read = |file_name: &str| std::fs::read_to_string(dir.path().join(file_name)).unwrap();
+  assert!(read("Cargo.toml").contains("name = \"demo-lib\"\n"));
+  assert!(!read("Cargo.toml").contains("[dev-dependencies]"));
+  assert!(read("LICENSE").contains("MIT License"));
+  assert!(read("README.md").contains("\nLicensed under the [MIT license][mit-url] (see [LICENSE][mit-license-url]).\n"));
+  assert!(read("README.md").contains("[mit-license-url]: https://github.com/john/demo/blob/main/LICENSE\n"));
+  assert!(dir.path().join("src/lib.rs").exists());
+  assert!(!dir.path().join("src/main.rs").exists());
+  assert!(!dir.path().join("tests").exists());
+  let taskfile = read("Taskfile.yml");
+  assert!(!taskfile.contains("\n  install:\n"));
+  assert!(!taskfile.contains("\n  uninstall:\n"));
+}
+
+#[test]
+fn existing_manifest() {
+  let dir = tempfile::TempDir::new().unwrap();
+  std::fs::write(dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("init")
+    .arg("--name")
+    .arg("demo")
+    .arg("--author")
+    .arg(AUTHOR)
+    .arg("--repository")
+    .arg(REPOSITORY)
+    .code(8)
+    .stdout("")
+    .stderr("error: ./Cargo.toml already exists\n")
+    .execute();
+}
+
+#[test]
+fn not_empty_directory() {
+  let dir = tempfile::TempDir::new().unwrap();
+  std::fs::create_dir(dir.path().join("demo")).unwrap();
+  std::fs::write(dir.path().join("demo/notes.txt"), "notes\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("new")
+    .arg("demo")
+    .arg("--author")
+    .arg(AUTHOR)
+    .arg("--repository")
+    .arg(REPOSITORY)
+    .code(8)
+    .stdout("")
+    .stderr("error: directory demo is not empty, use init command instead\n")
+    .execute();
+}
+
+#[test]
+fn missing_email() {
+  let dir = tempfile::TempDir::new().unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("new")
+    .arg("demo")
+    .arg("--author")
+    .arg("John Doe")
+    .arg("--repository")
+    .arg(REPOSITORY)
+    .code(8)
+    .stdout("")
+    .stderr("error: e-mail address of an author is required, like: --author 'Name <e-mail>'\n")
+    .execute();
+}
+
+#[test]
+fn dry_run() {
+  let dir = tempfile::TempDir::new().unwrap();
+  let mut command = cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("new")
+    .arg("demo")
+    .arg("--author")
+    .arg(AUTHOR)
+    .arg("--repository")
+    .arg(REPOSITORY)
+    .arg("--dry-run")
+    .code(0)
+    .stderr("");
+  command.execute();
+  let output = command.get_stdout();
+  assert!(output.contains("--- demo/Cargo.toml\n+++ demo/Cargo.toml\n@@ -0,0 +1,"));
+  assert!(output.contains("+### demo\n"));
+  assert!(output.ends_with("17 created, 0 updated, 0 unchanged (dry run)\n"));
+  assert!(!dir.path().join("demo").exists());
+}
+
+#[test]
+fn existing_file_not_overwritten() {
+  let dir = tempfile::TempDir::new().unwrap();
+  std::fs::write(dir.path().join(".gitignore"), "/build\n").unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("init")
+    .arg("--name")
+    .arg("demo")
+    .arg("--author")
+    .arg(AUTHOR)
+    .arg("--repository")
+    .arg(REPOSITORY)
+    .code(10)
+    .stderr("error: 1 file was not overwritten because of changed content, use --force to overwrite or --dry-run to display changes\n")
+    .execute();
+  assert_eq!("/build\n", std::fs::read_to_string(dir.path().join(".gitignore")).unwrap());
+  assert!(dir.path().join("src/main.rs").exists());
+}
@@ -0,0 +1,4 @@
+use super::*;
+
+mod test_members;
+mod test_paths;
@@ -0,0 +1,108 @@
+use super::*;
+
+#[test]
+fn all_members() {
+  let dir = fixture("test_workspace/project");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--workspace")
+    .code(0)
+    .stdout("created: crates/alpha/README.md\ncreated: crates/beta/README.md\n2 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  let alpha = read(&dir, "crates/alpha/README.md");
+  assert!(alpha.starts_with("### alpha\n"));
+  assert!(alpha.contains("[repository-url]: https://github.com/EngosSoftware/project\n"));
+  assert!(alpha.contains("## About alpha\n"));
+  let beta = read(&dir, "crates/beta/README.md");
+  assert!(beta.starts_with("### beta\n"));
+  assert!(beta.contains("## About beta\n"));
+}
+
+#[test]
+fn selected_member() {
+  let dir = fixture("test_workspace/project");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("-p")
+    .arg("beta")
+    .code(0)
+    .stdout("created: crates/beta/README.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(!dir.path().join("crates/alpha/README.md").exists());
+  assert!(dir.path().join("crates/beta/README.md").exists());
+}
+
+#[test]
+fn unknown_member() {
+  let dir = fixture("test_workspace/project");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("licenses")
+    .arg("-p")
+    .arg("gamma")
+    .code(8)
+    .stdout("")
+    .stderr("error: package 'gamma' not found in workspace\n")
+    .execute();
+}
+
+#[test]
+fn virtual_manifest() {
+  let dir = fixture("test_workspace/project");
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(4)
+    .stdout("")
+    .stderr("error: [package] section not found in Cargo.toml, use --workspace or --package for virtual manifests\n")
+    .execute();
+}
+
+#[test]
+fn inherited_from_member_directory() {
+  let dir = fixture("test_workspace/project");
+  cli_assert::command!()
+    .current_dir(dir.path().join("crates/alpha"))
+    .arg("readme")
+    .arg("docs/README.md")
+    .code(0)
+    .stdout("created: README.md\n1 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(read(&dir, "crates/alpha/README.md").contains("[cc-url]: https://github.com/EngosSoftware/project/blob/main/CODE_OF_CONDUCT.md\n"));
+}
+
+#[test]
+fn workflows_in_workspace_root() {
+  let dir = fixture("test_workspace/project");
+  std::fs::write(
+    dir.path().join("crates/alpha/README.md"),
+    "[![Linux](https://github.com/EngosSoftware/project/actions/workflows/build-linux.yml/badge.svg)](https://github.com/EngosSoftware/project/actions/workflows/build-linux.yml)\n",
+  )
+  .unwrap();
+  cli_assert::command!()
+    .current_dir(dir.path())
+    .arg("ci")
+    .arg("--workspace")
+    .code(0)
+    .stdout("created: .github/workflows/build-linux.yml\ncreated: .github/workflows/build-windows.yml\ncreated: .github/workflows/build-macos.yml\ncreated: .github/workflows/build-macos-arm64.yml\n4 created, 0 updated, 0 unchanged\n")
+    .stderr("")
+    .execute();
+  assert!(!dir.path().join("crates/alpha/.github").exists());
+  assert!(!dir.path().join("crates/beta/.github").exists());
+  cli_assert::command!()
+    .current_dir(dir.path().join("crates/beta"))
+    .arg("ci")
+    .arg("--check")
+    .code(0)
+    .stdout("")
+    .stderr("")
+    .execute();
+}
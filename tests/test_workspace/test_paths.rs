@@ -0,0 +1,61 @@
+use super::*;
+
+#[test]
+fn manifest_path_and_output_dir() {
+  let dir = fixture("test_workspace/project");
+  let output_dir = tempfile::TempDir::new().unwrap();
+  let expected = format!(
+    "created: {0}/crates/alpha/README.md\ncreated: {0}/crates/beta/README.md\n2 created, 0 updated, 0 unchanged\n",
+    output_dir.path().display()
+  );
+  cli_assert::command!()
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--workspace")
+    .arg("--manifest-path")
+    .arg(dir.path().join("Cargo.toml"))
+    .arg("--output-dir")
+    .arg(output_dir.path())
+    .code(0)
+    .stdout(expected)
+    .stderr("")
+    .execute();
+  assert!(read(&output_dir, "crates/alpha/README.md").starts_with("### alpha\n"));
+  assert!(read(&output_dir, "crates/beta/README.md").starts_with("### beta\n"));
+  assert!(!dir.path().join("crates/alpha/README.md").exists());
+}
+
+#[test]
+fn member_manifest_path() {
+  let dir = fixture("test_workspace/project");
+  cli_assert::command!()
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--manifest-path")
+    .arg(dir.path().join("crates/beta/Cargo.toml"))
+    .code(0)
+    .stdout(format!(
+      "created: {}\n1 created, 0 updated, 0 unchanged\n",
+      dir.path().join("crates/beta/README.md").display()
+    ))
+    .stderr("")
+    .execute();
+  assert!(read(&dir, "crates/beta/README.md").starts_with("### beta\n"));
+}
+
+#[test]
+fn invalid_manifest_path() {
+  let dir = fixture("test_workspace/project");
+  cli_assert::command!()
+    .arg("readme")
+    .arg("docs/README.md")
+    .arg("--manifest-path")
+    .arg(dir.path().join("crates"))
+    .code(8)
+    .stdout("")
+    .stderr(format!(
+      "error: the manifest path must be a path to a Cargo.toml file: {}\n",
+      dir.path().join("crates").display()
+    ))
+    .execute();
+}